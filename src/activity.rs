@@ -0,0 +1,166 @@
+use std::{fs, io::Write};
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Position,
+    style::Stylize,
+    text::{Line, Span},
+};
+use ratatui_textarea::TextArea;
+
+use crate::{App, InputMode, crossterm_to_input, dates, popup::show_popup};
+
+// Fields of an activity log line: time, board path and what happened
+const SEPARATOR: char = '\t';
+
+#[derive(Default)]
+pub struct ActivityView {
+    filter: TextArea<'static>,
+    scroll: usize,
+    // log entries read when the view opens, rather than on every redraw
+    entries: Vec<(String, String, String)>,
+}
+
+impl ActivityView {
+    fn filter(&self) -> String {
+        self.filter.lines().first().cloned().unwrap_or_default()
+    }
+}
+
+impl App {
+    /// Appends a timestamped line to the activity log, if there is one.
+    pub fn log_activity(&mut self, activity: &str) {
+        let Some(path) = &self.activity_log else {
+            return;
+        };
+        let trail = self.activity_trail();
+        let line = format!(
+            "{}{}{}{}{}\n",
+            dates::format_time(dates::now()),
+            SEPARATOR,
+            trail,
+            SEPARATOR,
+            activity
+        );
+        let result = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        if let Err(e) = result {
+            self.warning = Some(format!(
+                "Unable to write activity log {}: {}",
+                path.display(),
+                e
+            ));
+        }
+    }
+
+    /// The path of the active board as written to the activity log.
+    pub fn activity_trail(&self) -> String {
+        self.board_trail()
+            .join(&self.config.board_config.path_separator.0)
+    }
+
+    /// Logged time, board path and activity of every line, oldest first.
+    pub fn activity_entries(&self) -> Vec<(String, String, String)> {
+        let Some(log) = self
+            .activity_log
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return Vec::new();
+        };
+        log.lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, SEPARATOR);
+                Some((
+                    fields.next()?.to_string(),
+                    fields.next()?.to_string(),
+                    fields.next()?.to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Activities logged when the view was opened, newest first, keeping
+    /// those that contain every word of the filter.
+    pub fn activities(&self, filter: &str) -> Vec<&(String, String, String)> {
+        let filter = filter.to_lowercase();
+        self.activity_view
+            .entries
+            .iter()
+            .rev()
+            .filter(|(time, trail, activity)| {
+                let line = format!("{} {} {}", time, trail, activity).to_lowercase();
+                filter.split_whitespace().all(|word| line.contains(word))
+            })
+            .collect()
+    }
+
+    pub fn open_activity_log(&mut self) {
+        self.activity_view = ActivityView {
+            entries: self.activity_entries(),
+            ..Default::default()
+        };
+        self.input_mode = InputMode::Activity;
+    }
+
+    pub fn handle_activity_key(&mut self, key: KeyEvent) {
+        let view = &mut self.activity_view;
+        match key.code {
+            KeyCode::Esc => self.input_mode = InputMode::Normal,
+            KeyCode::Down => view.scroll += 1,
+            KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Enter => {}
+            _ => {
+                view.filter.input(crossterm_to_input(key));
+                view.scroll = 0;
+            }
+        }
+    }
+
+    pub fn draw_activity_popup(&mut self, frame: &mut Frame) {
+        let filter = self.activity_view.filter();
+        let activities = self.activities(&filter);
+        let rows = (frame.area().height as usize).saturating_sub(10).max(1);
+        let scroll = self
+            .activity_view
+            .scroll
+            .min(activities.len().saturating_sub(rows));
+
+        let mut lines = vec![
+            Line::from(vec![Span::raw("Filter: ").dark_gray(), Span::raw(&filter)]),
+            Line::raw(""),
+        ];
+        lines.extend(
+            activities
+                .iter()
+                .skip(scroll)
+                .take(rows)
+                .map(|(time, trail, activity)| {
+                    let mut spans = vec![Span::raw(format!("{}  ", time)).dark_gray()];
+                    if !trail.is_empty() {
+                        spans.push(Span::raw(format!("{}  ", trail)).yellow());
+                    }
+                    spans.push(Span::raw(activity.as_str()));
+                    Line::from(spans)
+                }),
+        );
+        if activities.is_empty() {
+            lines.push(Line::from("No activity yet").dark_gray());
+        }
+        let width = frame.area().width.saturating_sub(4).min(100);
+        let area = show_popup(
+            frame,
+            "Activity",
+            width,
+            lines,
+            "Type to filter, Up/Down to scroll, Esc close",
+        );
+        self.activity_view.scroll = scroll;
+        let cursor = "Filter: ".len() + self.activity_view.filter.cursor().1;
+        frame.set_cursor_position(Position::new(area.x + cursor as u16, area.y));
+    }
+}
@@ -0,0 +1,136 @@
+use std::{fs, path::PathBuf};
+
+use crate::commands::{ArchiveCommand, ArchivedItem};
+use crate::{App, Board, BoardList, dates};
+
+// Deep copies a board and all of its sub-boards into another boards vector
+fn copy_board_tree(from: &[Board], board: usize, to: &mut Vec<Board>) -> usize {
+    let mut copy = from[board].clone();
+    for list in copy.lists.iter_mut() {
+        for item in list.items.iter_mut() {
+            item.board = item.board.map(|b| copy_board_tree(from, b, to));
+        }
+    }
+    to.push(copy);
+    to.len() - 1
+}
+
+impl App {
+    pub fn archive_path(&self) -> PathBuf {
+        self.filename.with_extension("archive.md")
+    }
+
+    pub fn board_trail(&self) -> Vec<String> {
+        self.board_path
+            .iter()
+            .rev()
+            .filter_map(|r| {
+                let (list, item) = (r.source_list?, r.source_item?);
+                Some(
+                    self.boards[r.source_board].lists[list].items[item]
+                        .title()
+                        .to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn collect_done_items(
+        &self,
+        board: usize,
+        trail: &[String],
+        recursive: bool,
+        groups: &mut Vec<(String, Vec<ArchivedItem>)>,
+    ) {
+        for (l, list) in self.boards[board].lists.iter().enumerate() {
+            let done: Vec<ArchivedItem> = list
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.done)
+                .map(|(i, item)| ArchivedItem {
+                    board,
+                    list: l,
+                    item: i,
+                    value: item.clone(),
+                })
+                .collect();
+            if !done.is_empty() {
+                let mut label = trail.to_vec();
+                label.push(list.name.clone());
+                groups.push((label.join(&self.config.board_config.path_separator.0), done));
+            }
+            if recursive {
+                for item in list.items.iter().filter(|item| !item.done) {
+                    if let Some(sub_board) = item.board {
+                        let mut trail = trail.to_vec();
+                        trail.push(item.title().to_string());
+                        self.collect_done_items(sub_board, &trail, recursive, groups);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn archive_done_items(&mut self, recursive: bool) {
+        let (board, trail) = if recursive {
+            self.load_linked_files();
+            (0, Vec::new())
+        } else {
+            (self.active_board_index(), self.board_trail())
+        };
+        let mut groups = Vec::new();
+        self.collect_done_items(board, &trail, recursive, &mut groups);
+        if groups.is_empty() {
+            return;
+        }
+
+        let path = self.archive_path();
+        let before = fs::read_to_string(&path).ok();
+        let mut archive = App {
+            boards: vec![Board::default()],
+            ..Default::default()
+        };
+        if before.is_some()
+            && let Err(e) = archive.read_md(&path, 0)
+        {
+            self.warning = Some(format!("Unable to archive to {}: {}", path.display(), e));
+            return;
+        }
+        let date = dates::format(dates::today());
+        for (label, items) in groups.iter() {
+            let name = format!("{} {}", date, label);
+            let list = match archive.boards[0].lists.iter().position(|l| l.name == name) {
+                Some(list) => list,
+                None => {
+                    archive.boards[0].lists.push(BoardList::from_heading(&name));
+                    archive.boards[0].lists.len() - 1
+                }
+            };
+            for a in items.iter() {
+                let mut item = a.value.clone();
+                item.board = item
+                    .board
+                    .map(|b| copy_board_tree(&self.boards, b, &mut archive.boards));
+                archive.boards[0].lists[list].items.push(item);
+            }
+        }
+        let mut after = Vec::new();
+        archive.write_md_to(&mut after);
+        let after = String::from_utf8_lossy(&after).into_owned();
+        // the archive is written before any item leaves the board
+        if let Err(e) = fs::write(&path, &after) {
+            self.warning = Some(format!("Unable to write archive {}: {}", path.display(), e));
+            return;
+        }
+
+        self.apply_tree_command(Box::new(ArchiveCommand {
+            items: groups.into_iter().flat_map(|(_, items)| items).collect(),
+            path,
+            before,
+            after,
+            bookmark: self.active_board().get_selection_bookmark(),
+            warning: None,
+        }));
+    }
+}
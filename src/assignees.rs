@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    Frame,
+    style::Stylize,
+    text::{Line, Span},
+};
+
+use crate::{App, popup::show_popup};
+
+const UNASSIGNED: &str = "(unassigned)";
+
+impl App {
+    /// Open items per mentioned person across the whole tree, busiest first.
+    pub fn open_items_per_assignee(&self) -> Vec<(String, usize)> {
+        let mut counts = BTreeMap::new();
+        for item in self.tree_items(0).into_iter().filter(|item| !item.done) {
+            let mut names = item
+                .mentions()
+                .into_iter()
+                .map(|name| (name.to_lowercase(), name))
+                .collect::<BTreeMap<_, _>>();
+            if names.is_empty() {
+                names.insert(UNASSIGNED.to_string(), UNASSIGNED);
+            }
+            for (key, name) in names {
+                counts.entry(key).or_insert_with(|| (name.to_string(), 0)).1 += 1;
+            }
+        }
+        let mut counts = counts.into_values().collect::<Vec<_>>();
+        counts.sort_by(|(a, a_count), (b, b_count)| {
+            (a == UNASSIGNED)
+                .cmp(&(b == UNASSIGNED))
+                .then(b_count.cmp(a_count))
+        });
+        counts
+    }
+
+    pub fn draw_assignees_popup(&self, frame: &mut Frame) {
+        let mut lines = self
+            .open_items_per_assignee()
+            .into_iter()
+            .map(|(name, count)| {
+                Line::from(vec![
+                    Span::raw(format!("{:>4}  ", count)).bold().yellow(),
+                    Span::raw(if name == UNASSIGNED {
+                        name
+                    } else {
+                        format!("@{}", name)
+                    }),
+                ])
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            lines.push(Line::from("No open items").dark_gray());
+        }
+        show_popup(
+            frame,
+            "Open items per assignee",
+            50,
+            lines,
+            "Press any key to close",
+        );
+    }
+}
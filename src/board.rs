@@ -1,7 +1,11 @@
+use std::collections::BTreeMap;
+
 use crate::{
     commands::*,
     config::{BoardConfig, Styles},
+    dates,
     list::*,
+    sort::SortKey,
 };
 
 use ratatui::{
@@ -21,69 +25,148 @@ pub struct Board {
     pub column_title_areas: Vec<Rect>,
     pub column_item_areas: Vec<Rect>,
     pub editing_item_index: Option<usize>,
+    pub scroll_offset: usize,
 }
 
+const MARGIN_WIDTH: u16 = 2;
+const COLUMN_SPACING: u16 = 2;
+const MIN_COLUMN_WIDTH: u16 = 8;
+const COLUMN_WIDTH_STEP: u16 = 5;
+const COLLAPSED_COLUMN_WIDTH: u16 = 3;
+
 impl Board {
     pub fn with_config(mut self, config: BoardConfig) -> Self {
         self.config = config;
         self
     }
 
-    pub fn number_of_lists(&self) -> usize {
-        self.lists.len()
-    }
     pub fn move_down(&mut self) {
-        if self.current_list.is_some() {
-            self.lists[self.current_list.unwrap()].select_next()
+        if let Some(current) = self.current_list {
+            self.lists[current].select_next()
         }
     }
 
     pub fn move_up(&mut self) {
-        if self.current_list.is_some() {
-            self.lists[self.current_list.unwrap()].select_previous()
+        if let Some(current) = self.current_list {
+            self.lists[current].select_previous()
         }
     }
 
     pub fn move_right(&mut self) {
         if let Some(current) = self.current_list {
-            if current < self.lists.len() - 1 {
+            if let Some(next) = (current + 1..self.lists.len()).find(|&i| !self.lists[i].collapsed)
+            {
                 self.lists[current].state.borrow_mut().select(None);
-                self.current_list = Some(current + 1);
-                self.lists[current + 1].set_selection();
+                self.current_list = Some(next);
+                self.lists[next].set_selection();
             }
         } else if !self.lists.is_empty() {
-            self.current_list = Some(0);
+            self.current_list = Some(self.first_expanded_list());
         }
     }
 
     pub fn move_left(&mut self) {
         if let Some(current) = self.current_list {
-            if current > 0 {
+            if let Some(previous) = (0..current).rev().find(|&i| !self.lists[i].collapsed) {
                 self.lists[current].state.borrow_mut().select(None);
-                self.current_list = Some(current.saturating_sub(1));
-                self.lists[self.current_list.unwrap()].set_selection();
+                self.current_list = Some(previous);
+                self.lists[previous].set_selection();
             }
         } else if !self.lists.is_empty() {
-            self.current_list = Some(0);
+            self.current_list = Some(self.first_expanded_list());
         }
     }
 
-    fn render_items(&'_ self, list: usize) -> Vec<ListItem<'_>> {
-        let is_dimmable = {
-            let dim_tailing_items = self.config.dim_tailing_items;
-            let focused_list = self.current_list.unwrap_or(list + 1) == list;
-            let current_item = self
-                .current_list()
-                .and_then(|l| l.selected_item_index)
-                .unwrap_or(usize::MAX);
-
-            let list_exception = true;
-
-            move |index: usize| {
-                !(focused_list && index == current_item || !dim_tailing_items || !list_exception)
-            }
+    fn first_expanded_list(&self) -> usize {
+        self.lists.iter().position(|l| !l.collapsed).unwrap_or(0)
+    }
+
+    fn current_list_is_collapsed(&self) -> bool {
+        self.current_list().is_some_and(|l| l.collapsed)
+    }
+
+    pub fn move_page_down(&mut self) {
+        let page = self.page_size();
+        if let Some(current) = self.current_list_mut() {
+            current.select_next_page(page)
+        }
+    }
+
+    pub fn move_page_up(&mut self) {
+        let page = self.page_size();
+        if let Some(current) = self.current_list_mut() {
+            current.select_previous_page(page)
+        }
+    }
+
+    pub fn move_half_page_down(&mut self) {
+        let page = self.page_size().div_ceil(2);
+        if let Some(current) = self.current_list_mut() {
+            current.select_next_page(page)
+        }
+    }
+
+    pub fn move_half_page_up(&mut self) {
+        let page = self.page_size().div_ceil(2);
+        if let Some(current) = self.current_list_mut() {
+            current.select_previous_page(page)
+        }
+    }
+
+    pub fn move_to_top(&mut self) {
+        if let Some(current) = self.current_list_mut() {
+            current.select_first()
+        }
+    }
+
+    pub fn move_to_bottom(&mut self) {
+        if let Some(current) = self.current_list_mut() {
+            current.select_last()
+        }
+    }
+
+    /// Number of items of the current list that fit in its column, starting
+    /// from the first one in view.
+    fn page_size(&self) -> usize {
+        let Some(list_idx) = self.current_list else {
+            return 1;
         };
+        let list = &self.lists[list_idx];
+        let height = self
+            .column_item_areas
+            .get(list_idx + 1)
+            .map(|a| a.height as usize)
+            .unwrap_or_default();
+        let offset = list.state.borrow().offset();
+        let mut used = 0;
+        list.items
+            .iter()
+            .skip(offset)
+            .take_while(|i| {
+                used += i.height(list.width as usize, &self.config);
+                used <= height
+            })
+            .count()
+            .max(1)
+    }
+
+    /// In focus mode every item past the first few of a list is dimmed,
+    /// except for the selected one and the items of exempted lists.
+    fn is_dimmable(&self, list: usize, index: usize) -> bool {
+        let board_list = &self.lists[list];
+        let focus_items = board_list.focus.unwrap_or(self.config.focus_items);
+        let focused_item =
+            self.current_list == Some(list) && board_list.selected_item_index == Some(index);
+        let list_exception = self
+            .config
+            .focus_exempt_lists
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&board_list.name));
 
+        self.config.dim_tailing_items && !list_exception && !focused_item && index >= focus_items
+    }
+
+    fn render_items(&'_ self, list: usize) -> Vec<ListItem<'_>> {
         let column_width = self.lists[list].width as usize;
         let max_index = if Some(list) == self.current_list {
             self.editing_item_index.unwrap_or(usize::MAX)
@@ -96,10 +179,8 @@ impl Board {
             .iter()
             .enumerate()
             .filter(|(i, _)| *i <= max_index)
-            .filter(|(_, i)| i.text.to_lowercase().contains(&self.filter.to_lowercase()))
-            .map(|(index, i)| {
-                i.render(index, column_width, is_dimmable(index), &self.config.styles)
-            })
+            .filter(|(_, i)| i.matches(&self.filter, &self.config))
+            .map(|(index, i)| i.render(column_width, self.is_dimmable(list, index), &self.config))
             .collect()
     }
 
@@ -135,16 +216,16 @@ impl Board {
     pub fn prioritize_selected_item(&mut self) -> Option<Box<dyn Command>> {
         if let Some(current_list_index) = self.current_list {
             let current_list = self.current_list_mut().unwrap();
-            if let Some(selected_item_index) = current_list.selected_item_index {
-                if selected_item_index > 0 {
-                    let cmd = ShuffleItemCommand {
-                        list: current_list_index,
-                        from_index: selected_item_index,
-                        to_index: selected_item_index - 1,
-                        bookmark: self.get_selection_bookmark(),
-                    };
-                    return Some(Box::new(cmd));
-                }
+            if let Some(selected_item_index) = current_list.selected_item_index
+                && selected_item_index > 0
+            {
+                let cmd = ShuffleItemCommand {
+                    list: current_list_index,
+                    from_index: selected_item_index,
+                    to_index: selected_item_index - 1,
+                    bookmark: self.get_selection_bookmark(),
+                };
+                return Some(Box::new(cmd));
             }
         }
         None
@@ -153,60 +234,72 @@ impl Board {
     pub fn deprioritize_selected_item(&mut self) -> Option<Box<dyn Command>> {
         if let Some(current_list_index) = self.current_list {
             let current_list = self.current_list_mut().unwrap();
-            if let Some(selected_item_index) = current_list.selected_item_index {
-                if selected_item_index < current_list.items.len() - 1 {
-                    let cmd = ShuffleItemCommand {
-                        list: current_list_index,
-                        from_index: selected_item_index,
-                        to_index: selected_item_index + 1,
-                        bookmark: self.get_selection_bookmark(),
-                    };
-                    return Some(Box::new(cmd));
-                }
+            if let Some(selected_item_index) = current_list.selected_item_index
+                && selected_item_index < current_list.items.len() - 1
+            {
+                let cmd = ShuffleItemCommand {
+                    list: current_list_index,
+                    from_index: selected_item_index,
+                    to_index: selected_item_index + 1,
+                    bookmark: self.get_selection_bookmark(),
+                };
+                return Some(Box::new(cmd));
             }
         }
         None
     }
 
+    // The app warns about a full list before asking for these commands
+    fn accepts_items(&self, list: usize) -> bool {
+        !(self.config.enforce_wip_limits && self.lists[list].is_full())
+    }
+
     pub fn move_to_prev_list(&mut self, index: usize) -> Option<Box<dyn Command>> {
-        if let Some(current_list_index) = self.current_list {
-            if current_list_index > 0 {
-                let current_list = self.current_list_mut().unwrap();
-                if let Some(selected_item_index) = current_list.selected_item_index {
-                    let cmd = MoveItemCommand {
-                        from_list: current_list_index,
-                        to_list: current_list_index - 1,
-                        from_index: selected_item_index,
-                        to_index: index,
-                        bookmark: self.get_selection_bookmark(),
-                    };
-                    return Some(Box::new(cmd));
-                }
+        if let Some(current_list_index) = self.current_list
+            && current_list_index > 0
+            && self.accepts_items(current_list_index - 1)
+        {
+            let current_list = self.current_list_mut().unwrap();
+            if let Some(selected_item_index) = current_list.selected_item_index {
+                let cmd = MoveItemCommand {
+                    from_list: current_list_index,
+                    to_list: current_list_index - 1,
+                    from_index: selected_item_index,
+                    to_index: index,
+                    bookmark: self.get_selection_bookmark(),
+                };
+                return Some(Box::new(cmd));
             }
         }
         None
     }
 
     pub fn move_to_next_list(&mut self, target_index: usize) -> Option<Box<dyn Command>> {
-        if let Some(current_list_index) = self.current_list {
-            if current_list_index < self.lists.len() - 1 {
-                let current_list = self.current_list_mut().unwrap();
-                if let Some(selected_item_index) = current_list.selected_item_index {
-                    let cmd = MoveItemCommand {
-                        from_list: current_list_index,
-                        to_list: current_list_index + 1,
-                        from_index: selected_item_index,
-                        to_index: target_index,
-                        bookmark: self.get_selection_bookmark(),
-                    };
-                    return Some(Box::new(cmd));
-                }
+        if let Some(current_list_index) = self.current_list
+            && current_list_index < self.lists.len() - 1
+            && self.accepts_items(current_list_index + 1)
+        {
+            let current_list = self.current_list_mut().unwrap();
+            if let Some(selected_item_index) = current_list.selected_item_index {
+                let cmd = MoveItemCommand {
+                    from_list: current_list_index,
+                    to_list: current_list_index + 1,
+                    from_index: selected_item_index,
+                    to_index: target_index,
+                    bookmark: self.get_selection_bookmark(),
+                };
+                return Some(Box::new(cmd));
             }
         }
         None
     }
 
     pub fn insert_item_to_current_list(&mut self) -> Option<Box<dyn StagedCommand>> {
+        if self.current_list_is_collapsed()
+            || self.current_list.is_some_and(|l| !self.accepts_items(l))
+        {
+            return None;
+        }
         self.current_list
             .map(|current_list| {
                 let list = &mut self.lists[current_list];
@@ -267,6 +360,7 @@ impl Board {
                         list: current_list,
                         item: pos,
                         value: BoardItem::new(""),
+                        bookmark: self.get_selection_bookmark(),
                         last_clipboard: None,
                     }) as Box<dyn Command>
                 })
@@ -275,6 +369,9 @@ impl Board {
     }
 
     pub fn paste_item(&mut self) -> Option<Box<dyn Command>> {
+        if self.current_list.is_some_and(|l| !self.accepts_items(l)) {
+            return None;
+        }
         self.current_list
             .map(|current_list| {
                 let list = &mut self.lists[current_list];
@@ -289,6 +386,7 @@ impl Board {
                     list: current_list,
                     item: pos,
                     bookmark: self.get_selection_bookmark(),
+                    last_clipboard: None,
                 }) as Box<dyn Command>
             })
             .or_else(|| None)
@@ -330,55 +428,198 @@ impl Board {
                         list: current_list,
                         item: pos,
                         bookmark: self.get_selection_bookmark(),
+                        spawn: self.next_occurrence_of(current_list, pos),
                     }) as Box<dyn Command>
                 })
             })
             .or_else(|| None)
     }
 
+    /// The list that gets the next occurrence of an item when it is
+    /// completed, `None` when completing it doesn't add one.
+    pub fn next_occurrence_list(&self, list: usize, item: usize) -> Option<usize> {
+        self.next_occurrence_of(list, item).map(|spawn| spawn.list)
+    }
+
+    /// Adds the next occurrence of a recurring item that is about to be
+    /// completed. It goes to the end of the configured recurring list, or
+    /// takes the place of the completed item when there is no such list.
+    fn next_occurrence_of(&self, list: usize, item: usize) -> Option<AddItemCommand> {
+        let value = &self.lists[list].items[item];
+        if value.done {
+            return None;
+        }
+        let value = value.next_occurrence(dates::today())?;
+        let (list, item) = match self
+            .lists
+            .iter()
+            .position(|l| l.name == self.config.recurring_list)
+        {
+            Some(target) => (target, self.lists[target].items.len()),
+            None => (list, item),
+        };
+        Some(AddItemCommand {
+            list,
+            item,
+            value,
+            bookmark: self.get_selection_bookmark(),
+        })
+    }
+
     pub fn shuffle_list_forward(&mut self) -> Option<Box<dyn Command>> {
-        if let Some(current_list) = self.current_list {
-            if current_list > 0 {
-                let cmd = ShuffleListCommand {
-                    from_index: current_list,
-                    to_index: current_list - 1,
-                    bookmark: self.get_selection_bookmark(),
-                };
-                return Some(Box::new(cmd));
-            }
+        if let Some(current_list) = self.current_list
+            && current_list > 0
+        {
+            let cmd = ShuffleListCommand {
+                from_index: current_list,
+                to_index: current_list - 1,
+                bookmark: self.get_selection_bookmark(),
+            };
+            return Some(Box::new(cmd));
         }
         None
     }
 
     pub fn shuffle_list_back(&mut self) -> Option<Box<dyn Command>> {
-        if let Some(current_list) = self.current_list {
-            if current_list < self.lists.len() - 1 {
-                let cmd = ShuffleListCommand {
-                    from_index: current_list,
-                    to_index: current_list + 1,
-                    bookmark: self.get_selection_bookmark(),
-                };
-                return Some(Box::new(cmd));
-            }
+        if let Some(current_list) = self.current_list
+            && current_list < self.lists.len() - 1
+        {
+            let cmd = ShuffleListCommand {
+                from_index: current_list,
+                to_index: current_list + 1,
+                bookmark: self.get_selection_bookmark(),
+            };
+            return Some(Box::new(cmd));
         }
         None
     }
 
+    pub fn sort_current_list(
+        &self,
+        key: SortKey,
+        progress: impl Fn(&BoardItem) -> Option<f64>,
+    ) -> Option<Box<dyn Command>> {
+        let list = self.current_list?;
+        let order = self.lists[list].sort_order(key, progress);
+        if order.iter().enumerate().all(|(position, &i)| position == i) {
+            return None;
+        }
+        Some(Box::new(SortListCommand {
+            list,
+            order,
+            bookmark: self.get_selection_bookmark(),
+        }))
+    }
+
+    pub fn toggle_current_list_collapsed(&mut self) -> Option<Box<dyn Command>> {
+        self.current_list.map(|list| {
+            Box::new(CollapseListCommand {
+                lists: vec![list],
+                bookmark: self.get_selection_bookmark(),
+            }) as Box<dyn Command>
+        })
+    }
+
+    pub fn expand_all_lists(&mut self) -> Option<Box<dyn Command>> {
+        let lists = (0..self.lists.len())
+            .filter(|&i| self.lists[i].collapsed)
+            .collect::<Vec<_>>();
+        if lists.is_empty() {
+            return None;
+        }
+        Some(Box::new(CollapseListCommand {
+            lists,
+            bookmark: self.get_selection_bookmark(),
+        }))
+    }
+
+    pub fn widen_current_list(&mut self) -> Option<Box<dyn Command>> {
+        let width = self.current_list()?.width.saturating_add(COLUMN_WIDTH_STEP);
+        self.resize_current_list(Some(width))
+    }
+
+    pub fn narrow_current_list(&mut self) -> Option<Box<dyn Command>> {
+        let width = self
+            .current_list()?
+            .width
+            .saturating_sub(COLUMN_WIDTH_STEP)
+            .max(MIN_COLUMN_WIDTH);
+        self.resize_current_list(Some(width))
+    }
+
+    pub fn reset_current_list_width(&mut self) -> Option<Box<dyn Command>> {
+        self.current_list()?.width_override?;
+        self.resize_current_list(None)
+    }
+
+    fn resize_current_list(&mut self, width: Option<u16>) -> Option<Box<dyn Command>> {
+        let list = self.current_list?;
+        Some(Box::new(ResizeListCommand {
+            list,
+            old: self.lists[list].width_override,
+            new: width,
+            bookmark: self.get_selection_bookmark(),
+        }))
+    }
+
     pub fn edit_current_item(&mut self) -> Option<Box<dyn StagedCommand>> {
-        if let Some(list) = self.current_list {
-            if let Some(item) = self.lists[list].selected_item_index {
-                return Some(Box::new(ChangeTextCommand {
-                    list,
-                    item,
-                    old: self.current_raw_item_text().clone(),
-                    new: self.current_raw_item_text().clone(),
-                    bookmark: self.get_selection_bookmark(),
-                }));
-            }
+        if self.current_list_is_collapsed() {
+            return None;
+        }
+        if let Some(list) = self.current_list
+            && let Some(item) = self.lists[list].selected_item_index
+        {
+            return Some(Box::new(ChangeTextCommand {
+                list,
+                item,
+                old: self.current_raw_item_text().clone(),
+                new: self.current_raw_item_text().clone(),
+                bookmark: self.get_selection_bookmark(),
+            }));
         }
         None
     }
 
+    pub fn cycle_current_item_priority(&self) -> Option<Box<dyn Command>> {
+        if self.current_list_is_collapsed() {
+            return None;
+        }
+        let list = self.current_list?;
+        let item = self.lists[list].selected_item_index?;
+        let mut changed = self.lists[list].items.get(item)?.clone();
+        changed.set_priority(match changed.priority() {
+            None => Some(1),
+            Some(priority) if priority < LOWEST_PRIORITY => Some(priority + 1),
+            Some(_) => None,
+        });
+        Some(Box::new(ChangeTextCommand {
+            list,
+            item,
+            old: self.lists[list].items[item].text.clone(),
+            new: changed.text,
+            bookmark: self.get_selection_bookmark(),
+        }))
+    }
+
+    pub fn change_current_item_properties(
+        &self,
+        properties: BTreeMap<String, String>,
+    ) -> Option<Box<dyn Command>> {
+        let list = self.current_list?;
+        let item = self.lists[list].selected_item_index?;
+        let old = self.lists[list].items.get(item)?.properties.clone();
+        if old == properties {
+            return None;
+        }
+        Some(Box::new(ChangePropertiesCommand {
+            list,
+            item,
+            old,
+            new: properties,
+            bookmark: self.get_selection_bookmark(),
+        }))
+    }
+
     pub fn current_raw_item_text(&self) -> &String {
         let item = self.lists[self.current_list.unwrap()]
             .selected_item_index
@@ -412,15 +653,7 @@ impl Board {
 
         let mut offset_y: u16 = 0;
         for j in first_item_index..selected {
-            let text = &list.items[j].text;
-            let (s, o) = textwrap::unfill(text);
-            let wrapped_text = textwrap::wrap(
-                &s,
-                o.break_words(false)
-                    .word_splitter(textwrap::WordSplitter::NoHyphenation)
-                    .width(column_width - 1),
-            );
-            offset_y += wrapped_text.len() as u16 + 1;
+            offset_y += list.items[j].height(column_width, &self.config) as u16;
         }
 
         let text = textarea.lines().join("\n");
@@ -450,31 +683,22 @@ impl Board {
             .copied()
             .unwrap_or_default();
         let column_width = list.width as usize;
-        let styles = &self.config.styles;
 
         let list_state = &list.state.borrow();
         let first_item_index = list_state.offset();
         let selected = list.selected_item_index.unwrap();
 
-        let dim_tailing_items = self.config.dim_tailing_items;
-        let focused_list = self.current_list.unwrap_or(list_idx + 1) == list_idx;
-        let current_item = self
-            .current_list()
-            .and_then(|l| l.selected_item_index)
-            .unwrap_or(usize::MAX);
-        let list_exception = true;
-
-        let is_dimmable = |index: usize| {
-            !(focused_list && index == current_item || !dim_tailing_items || !list_exception)
-        };
-
         let mut cursor_y = textarea_rect.y + textarea_rect.height;
 
         for j in (selected + 1)..list.items.len() {
             if j < first_item_index {
                 continue;
             }
-            let styled_text = list.items[j].styled_text(j, column_width, is_dimmable(j), styles);
+            let styled_text = list.items[j].styled_text(
+                column_width,
+                self.is_dimmable(list_idx, j),
+                &self.config,
+            );
 
             for line in styled_text.lines.iter() {
                 if cursor_y >= item_area.y + item_area.height {
@@ -488,6 +712,97 @@ impl Board {
         }
     }
 
+    fn column_widths(&self, available: u16) -> Vec<u16> {
+        let columns = &self.config.columns;
+        let flexible_width = if columns.fit_to_screen {
+            let fixed: u16 = self
+                .lists
+                .iter()
+                .filter_map(|l| {
+                    if l.collapsed {
+                        Some(COLLAPSED_COLUMN_WIDTH)
+                    } else {
+                        l.width_override
+                    }
+                })
+                .map(|w| w.saturating_add(COLUMN_SPACING))
+                .fold(0, u16::saturating_add);
+            let flexible = self
+                .lists
+                .iter()
+                .filter(|l| l.width_override.is_none() && !l.collapsed)
+                .count()
+                .max(1) as u16;
+            (available.saturating_sub(fixed) / flexible).saturating_sub(COLUMN_SPACING)
+        } else {
+            columns.max_width
+        };
+        let flexible_width = flexible_width
+            .min(columns.max_width)
+            .max(columns.min_width)
+            .max(MIN_COLUMN_WIDTH);
+        self.lists
+            .iter()
+            .map(|l| {
+                if l.collapsed {
+                    COLLAPSED_COLUMN_WIDTH
+                } else {
+                    l.width_override
+                        .unwrap_or(flexible_width)
+                        .max(MIN_COLUMN_WIDTH)
+                }
+            })
+            .collect()
+    }
+
+    fn scroll_to_current_list(&mut self, widths: &[u16], available: u16) {
+        let span = |lists: &[u16]| {
+            lists
+                .iter()
+                .map(|w| (w + COLUMN_SPACING) as usize)
+                .sum::<usize>()
+        };
+        let available = available as usize;
+        let current = self
+            .current_list
+            .unwrap_or(0)
+            .min(widths.len().saturating_sub(1));
+        self.scroll_offset = self.scroll_offset.min(current);
+        while self.scroll_offset < current
+            && span(&widths[self.scroll_offset..=current]) > available
+        {
+            self.scroll_offset += 1;
+        }
+        while self.scroll_offset > 0 && span(&widths[self.scroll_offset - 1..]) <= available {
+            self.scroll_offset -= 1;
+        }
+    }
+
+    fn draw_collapsed_list(&self, frame: &mut Frame, list: usize, top: Rect, center: Rect) {
+        let styles = &self.config.styles;
+        let style = if self.current_list == Some(list) {
+            Style::default()
+                .fg(styles.active_header.fg)
+                .bg(styles.active_header.bg)
+                .underlined()
+        } else {
+            Style::default().fg(styles.header.fg).bg(styles.header.bg)
+        };
+        let count = Line::from(self.lists[list].items.len().to_string())
+            .dim()
+            .apply_if(self.lists[list].is_over_wip_limit(), |l| {
+                l.fg(styles.wip_exceeded.fg).bg(styles.wip_exceeded.bg)
+            });
+        frame.render_widget(Paragraph::new(vec![Line::raw(""), count]).centered(), top);
+        let name = self.lists[list]
+            .name
+            .to_uppercase()
+            .chars()
+            .map(|c| Line::from(c.to_string()).style(style).bold())
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(name).centered(), center);
+    }
+
     pub fn draw(&mut self, frame: &mut Frame, rect: Rect) -> Rect {
         if self.lists.is_empty() {
             let layout = Layout::vertical([
@@ -505,49 +820,122 @@ impl Board {
             return rect;
         }
 
-        let constraints = std::iter::once(Constraint::Length(2))
-            .chain((0..self.number_of_lists()).map(|_| Constraint::Length(50)))
-            .collect::<Vec<_>>();
-        let vertical_layout = Layout::horizontal(constraints).spacing(2);
-        let horizontal_layout = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]);
+        let available = rect.width.saturating_sub(MARGIN_WIDTH);
+        let widths = self.column_widths(available);
+        self.scroll_to_current_list(&widths, available);
 
+        let horizontal_layout = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]);
         let [top, center] = horizontal_layout.areas(rect);
-        let columns_top = vertical_layout.split(top);
-        let columns_center = vertical_layout.split(center);
 
-        self.column_title_areas = columns_top.to_vec();
-        self.column_item_areas = columns_center.to_vec();
+        self.column_title_areas = vec![Rect::new(top.x, top.y, MARGIN_WIDTH, top.height)];
+        self.column_item_areas = vec![Rect::new(center.x, center.y, MARGIN_WIDTH, center.height)];
+        let mut x = rect.x + MARGIN_WIDTH;
+        let mut hidden_on_right = false;
+        for (i, width) in widths.iter().enumerate() {
+            let column_x = x + COLUMN_SPACING;
+            let fits = column_x + width <= rect.right();
+            if i >= self.scroll_offset && !hidden_on_right && (fits || i == self.scroll_offset) {
+                let width = (*width).min(rect.right().saturating_sub(column_x));
+                self.column_title_areas
+                    .push(Rect::new(column_x, top.y, width, top.height));
+                self.column_item_areas.push(Rect::new(
+                    column_x,
+                    center.y,
+                    width,
+                    center.height.saturating_sub(1),
+                ));
+                x = column_x + width;
+            } else {
+                hidden_on_right |= i >= self.scroll_offset;
+                self.column_title_areas.push(Rect::default());
+                self.column_item_areas.push(Rect::default());
+            }
+        }
+
+        let indicator_style =
+            Style::default()
+                .fg(self.config.styles.header.fg)
+                .bg(self.config.styles.header.bg);
+        if self.scroll_offset > 0 {
+            frame.render_widget(
+                Paragraph::new("◀").style(indicator_style),
+                Rect::new(top.x, top.y + 1, 1, 1),
+            );
+        }
+        if hidden_on_right {
+            frame.render_widget(
+                Paragraph::new("▶").style(indicator_style),
+                Rect::new(top.right().saturating_sub(1), top.y + 1, 1, 1),
+            );
+        }
 
         for (i, list) in self.lists.iter().enumerate() {
-            let col = columns_top[i + 1];
+            let col = self.column_title_areas[i + 1];
+            if col.is_empty() {
+                continue;
+            }
+            if list.collapsed {
+                self.draw_collapsed_list(frame, i, col, self.column_item_areas[i + 1]);
+                continue;
+            }
+            let styles = &self.config.styles;
+            let (header, active_header) = if list.is_over_wip_limit() {
+                (&styles.wip_exceeded, &styles.wip_exceeded)
+            } else {
+                (&styles.header, &styles.active_header)
+            };
             let lines = vec![
                 Line::raw(""),
-                Line::from(list.name.clone().to_uppercase())
-                    .fg(self.config.styles.header.fg)
-                    .bg(self.config.styles.header.bg)
+                Line::from(list.title())
+                    .fg(header.fg)
+                    .bg(header.bg)
                     .bold()
                     .apply_if(self.current_list == Some(i), |l| {
-                        l.underlined()
-                            .bg(self.config.styles.active_header.bg)
-                            .fg(self.config.styles.active_header.fg)
+                        l.underlined().bg(active_header.bg).fg(active_header.fg)
                     }),
             ];
             frame.render_widget(Paragraph::new(lines), col);
         }
 
-        self.lists
-            .iter_mut()
-            .enumerate()
-            .for_each(|(i, list)| list.width = self.column_item_areas[i + 1].width);
+        for (i, list) in self.lists.iter_mut().enumerate() {
+            let area = self.column_item_areas[i + 1];
+            list.width = if area.is_empty() {
+                widths[i]
+            } else {
+                area.width
+            };
+        }
 
         for (i, list) in self.lists.iter().enumerate() {
-            render_list(
+            let area = self.column_item_areas[i + 1];
+            if area.is_empty() || list.collapsed {
+                continue;
+            }
+            let (above, below) = render_list(
                 frame,
-                self.column_item_areas[i + 1],
+                area,
                 &mut list.state.borrow_mut(),
                 self.render_items(i),
                 &self.config.styles,
             );
+            if above > 0 {
+                frame.render_widget(
+                    Paragraph::new(format!("↑ {} more", above))
+                        .style(indicator_style)
+                        .dim()
+                        .right_aligned(),
+                    Rect::new(area.x, area.y.saturating_sub(1), area.width, 1),
+                );
+            }
+            if below > 0 {
+                frame.render_widget(
+                    Paragraph::new(format!("↓ {} more", below))
+                        .style(indicator_style)
+                        .dim()
+                        .right_aligned(),
+                    Rect::new(area.x, area.bottom(), area.width, 1),
+                );
+            }
         }
 
         *self
@@ -567,18 +955,32 @@ impl ApplyIf for Line<'_> {
     }
 }
 
+/// Renders the list items and returns how many of them are scrolled out of
+/// view above and below the area.
 pub fn render_list(
     frame: &mut Frame,
     area: Rect,
     list_state: &mut ListState,
     modded_items: Vec<ListItem>,
     styles: &Styles,
-) {
+) -> (usize, usize) {
+    let heights = modded_items.iter().map(|i| i.height()).collect::<Vec<_>>();
     let selected_type = Style::default().bg(styles.selected.bg);
     let list = List::new(modded_items)
         .style(Color::White)
         .highlight_style(selected_type);
     frame.render_stateful_widget(list, area, list_state);
+
+    let above = list_state.offset().min(heights.len());
+    let mut used = 0;
+    let visible = heights[above..]
+        .iter()
+        .take_while(|h| {
+            used += **h;
+            used <= area.height as usize
+        })
+        .count();
+    (above, heights.len() - above - visible)
 }
 
 #[cfg(test)]
@@ -639,7 +1041,7 @@ mod tests {
                 clipboard: None,
             }
         }
-        pub fn with_clipboard(mut self, content: String) -> Self {
+        pub fn with_clipboard(mut self, content: BoardItem) -> Self {
             self.clipboard = Some(content);
             self
         }
@@ -782,7 +1184,7 @@ mod tests {
             let mut context = Context::from_board(&mut board);
             cmd.unwrap().apply(&mut context);
             let clipboard_content = context.clipboard.unwrap();
-            assert_eq!(clipboard_content, "list 1 item 1");
+            assert_eq!(clipboard_content.text, "list 1 item 1");
             clipboard_content
         };
         {
@@ -794,6 +1196,68 @@ mod tests {
         assert_eq!(board.current_list_mut().unwrap().items.len(), 4);
     }
 
+    #[test]
+    fn test_cut_and_paste_keeps_properties() {
+        let mut board = boards_with_two_short_lists();
+        board.lists[0].items[0]
+            .properties
+            .insert("id".to_string(), "a1b2".to_string());
+        board.lists[0].items[0]
+            .properties
+            .insert("spent".to_string(), "1h".to_string());
+        let cut = {
+            let cmd = board.cut_selected_item();
+            let mut context = Context::from_board(&mut board);
+            cmd.unwrap().apply(&mut context);
+            context.clipboard.unwrap()
+        };
+        board.move_right();
+        let mut context = Context::from_board(&mut board).with_clipboard(cut);
+        let mut first = context.board.paste_item().unwrap();
+        first.apply(&mut context);
+        let mut second = context.board.paste_item().unwrap();
+        second.apply(&mut context);
+        let pasted = &context.board.lists[1].items[1];
+        assert_eq!(pasted.text, "list 1 item 1");
+        assert_eq!(pasted.properties["id"], "a1b2");
+        assert_eq!(pasted.properties["spent"], "1h");
+
+        // a second paste is a copy, so the id stays unique
+        let copy = &context.board.lists[1].items[2];
+        assert_eq!(copy.text, "list 1 item 1");
+        assert!(!copy.properties.contains_key("id"));
+        assert_eq!(copy.properties["spent"], "1h");
+
+        second.revert(&mut context);
+        first.revert(&mut context);
+        assert_eq!(context.clipboard.unwrap().properties["id"], "a1b2");
+    }
+
+    #[test]
+    fn test_yank_leaves_identity_properties_behind() {
+        let mut board = boards_with_two_short_lists();
+        board.lists[0].items[0]
+            .properties
+            .insert("id".to_string(), "a1b2".to_string());
+        board.lists[0].items[0]
+            .properties
+            .insert("spent".to_string(), "1h".to_string());
+        board.lists[0].items[0]
+            .properties
+            .insert("file".to_string(), "todo-sub.md".to_string());
+        board.lists[0].items[0]
+            .properties
+            .insert("started".to_string(), "2026-10-19 09:00".to_string());
+        let cmd = board.yank_selected_item();
+        let mut context = Context::from_board(&mut board);
+        cmd.unwrap().apply(&mut context);
+        let copy = context.clipboard.unwrap();
+        assert!(!copy.properties.contains_key("id"));
+        assert!(!copy.properties.contains_key("file"));
+        assert!(!copy.properties.contains_key("started"));
+        assert_eq!(copy.properties["spent"], "1h");
+    }
+
     #[test]
     fn test_shuffle_list_forward() {
         let mut board = board_with_empty_lists();
@@ -829,4 +1293,274 @@ mod tests {
         let cmd = board.shuffle_list_back();
         assert!(cmd.is_none());
     }
+
+    fn board_with_columns(columns: crate::config::Columns, lists: usize) -> Board {
+        Board {
+            lists: (0..lists).map(|_| BoardList::default()).collect(),
+            current_list: Some(0),
+            ..Default::default()
+        }
+        .with_config(BoardConfig {
+            columns,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let mut board = board_with_columns(Default::default(), 3);
+        board.lists[1].width_override = Some(30);
+        assert_eq!(board.column_widths(100), vec![50, 30, 50]);
+
+        board.config.columns.fit_to_screen = true;
+        assert_eq!(board.column_widths(100), vec![32, 30, 32]);
+        assert_eq!(board.column_widths(40), vec![20, 30, 20]);
+    }
+
+    #[test]
+    fn test_scroll_keeps_current_list_visible() {
+        let mut board = board_with_columns(Default::default(), 4);
+        let widths = board.column_widths(110);
+        board.current_list = Some(3);
+        board.scroll_to_current_list(&widths, 110);
+        assert_eq!(board.scroll_offset, 2);
+        board.current_list = Some(1);
+        board.scroll_to_current_list(&widths, 110);
+        assert_eq!(board.scroll_offset, 1);
+        board.lists.truncate(2);
+        let widths = board.column_widths(110);
+        board.scroll_to_current_list(&widths, 110);
+        assert_eq!(board.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_resize_current_list() {
+        let mut board = board_with_empty_lists();
+        board.lists[0].width = 50;
+        let mut cmd = board.widen_current_list().unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[0].width_override, Some(55));
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[0].width_override, None);
+        assert!(board.reset_current_list_width().is_none());
+    }
+
+    #[test]
+    fn test_page_navigation() {
+        let mut board = Board {
+            lists: vec![BoardList {
+                items: (1..=20)
+                    .map(|i| BoardItem::new(&format!("item {}", i)))
+                    .collect(),
+                selected_item_index: Some(0),
+                width: 20,
+                ..Default::default()
+            }],
+            current_list: Some(0),
+            column_item_areas: vec![Rect::default(), Rect::new(4, 3, 20, 10)],
+            ..Default::default()
+        };
+        board.move_page_down();
+        assert_eq!(board.get_current_selection_index(), 5);
+        board.move_half_page_down();
+        assert_eq!(board.get_current_selection_index(), 8);
+        board.move_half_page_up();
+        assert_eq!(board.get_current_selection_index(), 5);
+        board.move_to_bottom();
+        assert_eq!(board.get_current_selection_index(), 19);
+        board.move_page_up();
+        assert_eq!(board.get_current_selection_index(), 14);
+        board.move_to_top();
+        assert_eq!(board.get_current_selection_index(), 0);
+    }
+
+    #[test]
+    fn test_move_skips_collapsed_lists() {
+        let mut board = board_with_empty_lists();
+        board.lists[1].collapsed = true;
+        board.move_right();
+        assert_eq!(board.current_list, Some(2));
+        board.move_left();
+        assert_eq!(board.current_list, Some(0));
+
+        board.lists[2].collapsed = true;
+        board.move_right();
+        assert_eq!(board.current_list, Some(0));
+    }
+
+    #[test]
+    fn test_collapse_and_expand_lists() {
+        let mut board = board_with_empty_lists();
+        let cmd = board.toggle_current_list_collapsed();
+        cmd.unwrap().apply(&mut Context::from_board(&mut board));
+        assert!(board.lists[0].collapsed);
+        assert!(board.insert_item_to_current_list().is_none());
+        board.current_list = Some(2);
+        board
+            .toggle_current_list_collapsed()
+            .unwrap()
+            .apply(&mut Context::from_board(&mut board));
+        assert_eq!(board.column_widths(100), vec![3, 50, 3]);
+
+        let mut cmd = board.expand_all_lists().unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        assert!(board.lists.iter().all(|l| !l.collapsed));
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert!(board.lists[0].collapsed && board.lists[2].collapsed);
+        assert_eq!(board.current_list, Some(2));
+    }
+
+    #[test]
+    fn test_focus_mode_dims_tailing_items() {
+        let mut board = boards_with_two_short_lists();
+        assert!(!board.is_dimmable(0, 2));
+
+        board.config.dim_tailing_items = true;
+        board.lists[0].selected_item_index = Some(2);
+        assert!(!board.is_dimmable(0, 0));
+        assert!(board.is_dimmable(0, 1));
+        assert!(!board.is_dimmable(0, 2));
+        assert!(board.is_dimmable(1, 2));
+
+        board.lists[1].focus = Some(3);
+        assert!(!board.is_dimmable(1, 2));
+
+        board.config.focus_exempt_lists = vec!["LIST 1".to_string()];
+        assert!(!board.is_dimmable(0, 1));
+    }
+
+    #[test]
+    fn test_sort_current_list() {
+        let mut board = board_with_a_short_list();
+        board.lists[0].items[0].done = true;
+        board.move_down();
+        let mut cmd = board
+            .sort_current_list(SortKey::DoneLast, |_| None)
+            .unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        let texts = |board: &Board| {
+            board.lists[0]
+                .items
+                .iter()
+                .map(|i| i.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(texts(&board), vec!["item 2", "item 3", "item 1"]);
+        assert_eq!(board.get_current_selection_index(), 0);
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert_eq!(texts(&board), vec!["item 1", "item 2", "item 3"]);
+        assert_eq!(board.get_current_selection_index(), 1);
+        assert!(
+            board
+                .sort_current_list(SortKey::Alphabetical, |_| None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_cycle_current_item_priority() {
+        let mut board = board_with_a_short_list();
+        board.move_down();
+        for expected in ["!1 item 2", "!2 item 2", "!3 item 2", "item 2"] {
+            let mut cmd = board.cycle_current_item_priority().unwrap();
+            cmd.apply(&mut Context::from_board(&mut board));
+            assert_eq!(board.lists[0].items[1].text, expected);
+        }
+        let mut cmd = board.cycle_current_item_priority().unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[0].items[1].text, "item 2");
+    }
+
+    #[test]
+    fn test_wip_limits_block_moves_and_inserts() {
+        let mut board = boards_with_two_short_lists();
+        board.lists[1].wip_limit = Some(3);
+        assert!(board.move_to_next_list(0).is_some());
+        board.config.enforce_wip_limits = true;
+        assert!(board.move_to_next_list(0).is_none());
+        board.move_right();
+        assert!(board.insert_item_to_current_list().is_none());
+        assert!(board.paste_item().is_none());
+        assert!(board.move_to_prev_list(0).is_some());
+    }
+
+    #[test]
+    fn test_change_current_item_properties() {
+        let mut board = board_with_a_short_list();
+        let mut properties = BTreeMap::new();
+        properties.insert("owner".to_string(), "alice".to_string());
+        let mut cmd = board
+            .change_current_item_properties(properties.clone())
+            .unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[0].items[0].properties, properties);
+        assert!(board.change_current_item_properties(properties).is_none());
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert!(board.lists[0].items[0].properties.is_empty());
+    }
+
+    #[test]
+    fn test_move_activity() {
+        let mut board = boards_with_two_short_lists();
+        let mut cmd = board.move_to_next_list(0).unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        assert_eq!(
+            cmd.activity(&board).as_deref(),
+            Some("moved \"list 1 item 1\" from list 1 to list 2")
+        );
+        assert!(
+            board
+                .shuffle_list_forward()
+                .unwrap()
+                .activity(&board)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_toggle_recurring_item() {
+        let mut board = boards_with_two_short_lists();
+        board.lists[0].items[0].text = "Chore every:week due:2026-10-16".to_string();
+        let mut cmd = board.toggle_selected_item().unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        let texts = |board: &Board, list: usize| {
+            board.lists[list]
+                .items
+                .iter()
+                .map(|item| (item.text.clone(), item.done))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(&board, 0)[0],
+            ("Chore every:week due:2026-10-23".to_string(), false)
+        );
+        assert_eq!(
+            texts(&board, 0)[1],
+            ("Chore every:week due:2026-10-16".to_string(), true)
+        );
+        assert_eq!(board.lists[0].items.len(), 4);
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[0].items.len(), 3);
+        assert!(!board.lists[0].items[0].done);
+
+        board.config.recurring_list = "list 2".to_string();
+        assert_eq!(board.next_occurrence_list(0, 0), Some(1));
+        assert_eq!(board.next_occurrence_list(0, 1), None);
+        let mut cmd = board.toggle_selected_item().unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        assert!(board.lists[0].items[0].done);
+        assert_eq!(
+            texts(&board, 1)[3],
+            ("Chore every:week due:2026-10-23".to_string(), false)
+        );
+        cmd.revert(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[1].items.len(), 3);
+
+        // reopening a done recurring item spawns nothing
+        board.lists[0].items[0].done = true;
+        let mut cmd = board.toggle_selected_item().unwrap();
+        cmd.apply(&mut Context::from_board(&mut board));
+        assert_eq!(board.lists[1].items.len(), 3);
+    }
 }
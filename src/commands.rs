@@ -1,4 +1,5 @@
-use crate::{board::Board, BoardItem, BoardList};
+use crate::{BoardItem, BoardList, board::Board, dates};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 #[derive(Clone)]
 pub struct SelectionBookmark {
@@ -8,7 +9,7 @@ pub struct SelectionBookmark {
 
 pub struct Context<'a> {
     pub board: &'a mut Board,
-    pub clipboard: Option<String>,
+    pub clipboard: Option<BoardItem>,
 }
 
 impl SelectionBookmark {
@@ -17,6 +18,11 @@ impl SelectionBookmark {
     }
     pub fn select_with_offset(&self, board: &mut Board, offset: i32) {
         if let Some(list) = self.list {
+            for (i, other) in board.lists.iter().enumerate() {
+                if i != list {
+                    other.state.borrow_mut().select(None);
+                }
+            }
             if list < board.lists.len() {
                 board.current_list = Some(list);
                 if let Some(item) = self.item {
@@ -27,10 +33,10 @@ impl SelectionBookmark {
                             board.lists[list].set_selection();
                         }
                     } else {
-                        board.lists[list].selected_item_index = None;
+                        board.lists[list].clear_selection();
                     }
                 } else {
-                    board.lists[list].selected_item_index = None;
+                    board.lists[list].clear_selection();
                 }
             } else {
                 board.current_list = None;
@@ -42,6 +48,39 @@ impl SelectionBookmark {
 pub trait Command {
     fn apply(&mut self, context: &mut Context);
     fn revert(&mut self, context: &mut Context);
+    /// Describes the change for the activity log, given the board it was
+    /// just applied to. Commands not worth logging keep the default.
+    fn activity(&self, _board: &Board) -> Option<String> {
+        None
+    }
+}
+
+// The first line of an item, quoted for the activity log, after the
+// `[[id]]` the flow report follows the item by when it has one
+pub fn item_label(item: &BoardItem) -> String {
+    let title = format!("\"{}\"", item.title());
+    match item.id() {
+        Some(id) => format!("[[{}]] {}", id, title),
+        None => title,
+    }
+}
+
+pub struct TreeContext<'a> {
+    pub boards: &'a mut Vec<Board>,
+    pub board: usize,
+}
+
+// Commands touching more than one board of the tree at once
+pub trait TreeCommand {
+    fn apply(&mut self, context: &mut TreeContext);
+    fn revert(&mut self, context: &mut TreeContext);
+    fn activity(&self) -> Option<String> {
+        None
+    }
+    /// A problem met by the last apply or revert, shown in the status line.
+    fn take_warning(&mut self) -> Option<String> {
+        None
+    }
 }
 
 pub trait StagedCommand: Command {
@@ -71,6 +110,7 @@ impl Command for ChangeTextCommand {
 
 impl StagedCommand for ChangeTextCommand {
     fn finalize(&mut self, board: &mut Board) -> bool {
+        board.lists[self.list].items[self.item].normalize_due_date(dates::today());
         self.new = board.lists[self.list].items[self.item].text.clone();
         self.new != self.old
     }
@@ -79,6 +119,25 @@ impl StagedCommand for ChangeTextCommand {
     }
 }
 
+pub struct ChangePropertiesCommand {
+    pub list: usize,
+    pub item: usize,
+    pub old: BTreeMap<String, String>,
+    pub new: BTreeMap<String, String>,
+    pub bookmark: SelectionBookmark,
+}
+
+impl Command for ChangePropertiesCommand {
+    fn apply(&mut self, context: &mut Context) {
+        context.board.lists[self.list].items[self.item].properties = self.new.clone();
+        self.bookmark.select(context.board);
+    }
+    fn revert(&mut self, context: &mut Context) {
+        context.board.lists[self.list].items[self.item].properties = self.old.clone();
+        self.bookmark.select(context.board);
+    }
+}
+
 #[derive(Clone)]
 pub struct AddListCommand {
     pub list: usize,
@@ -88,13 +147,10 @@ pub struct AddListCommand {
 
 impl Command for AddListCommand {
     fn apply(&mut self, context: &mut Context) {
-        context.board.lists.insert(
-            self.list,
-            BoardList {
-                name: self.title.clone(),
-                ..Default::default()
-            },
-        );
+        context
+            .board
+            .lists
+            .insert(self.list, BoardList::from_heading(&self.title));
         self.bookmark.select(context.board);
     }
     fn revert(&mut self, context: &mut Context) {
@@ -112,7 +168,13 @@ pub struct DeleteListCommand {
 impl Command for DeleteListCommand {
     fn apply(&mut self, context: &mut Context) {
         self.value = context.board.lists.remove(self.list);
-        self.bookmark.select(context.board);
+        if context.board.lists.is_empty() {
+            context.board.current_list = None;
+        } else {
+            let list = self.list.min(context.board.lists.len() - 1);
+            context.board.current_list = Some(list);
+            context.board.lists[list].set_selection();
+        }
     }
     fn revert(&mut self, context: &mut Context) {
         context.board.lists.insert(self.list, self.value.clone());
@@ -127,6 +189,7 @@ impl Command for DeleteListCommand {
 impl StagedCommand for AddListCommand {
     fn finalize(&mut self, board: &mut Board) -> bool {
         self.title = board.lists[self.list].name.clone();
+        board.lists[self.list] = BoardList::from_heading(&self.title);
         !self.title.is_empty()
     }
     fn to_cmd(&self) -> Box<dyn Command> {
@@ -153,10 +216,19 @@ impl Command for AddItemCommand {
         context.board.lists[self.list].items.remove(self.item);
         self.bookmark.select_with_offset(context.board, -1);
     }
+    fn activity(&self, board: &Board) -> Option<String> {
+        let list = &board.lists[self.list];
+        Some(format!(
+            "created {} in {}",
+            item_label(list.items.get(self.item)?),
+            list.name
+        ))
+    }
 }
 
 impl StagedCommand for AddItemCommand {
     fn finalize(&mut self, board: &mut Board) -> bool {
+        board.lists[self.list].items[self.item].normalize_due_date(dates::today());
         self.value = board.lists[self.list].items[self.item].clone();
         !self.value.text.is_empty()
     }
@@ -175,6 +247,7 @@ pub struct DeleteItemCommand {
 impl Command for DeleteItemCommand {
     fn apply(&mut self, context: &mut Context) {
         self.value = context.board.lists[self.list].items.remove(self.item);
+        self.bookmark.select(context.board);
         context.board.lists[self.list].set_selection_index(self.item.saturating_sub(1));
         context.board.lists[self.list].set_selection();
     }
@@ -184,6 +257,13 @@ impl Command for DeleteItemCommand {
             .insert(self.item, self.value.clone());
         self.bookmark.select(context.board);
     }
+    fn activity(&self, board: &Board) -> Option<String> {
+        Some(format!(
+            "deleted {} from {}",
+            item_label(&self.value),
+            board.lists[self.list].name
+        ))
+    }
 }
 
 pub struct ShuffleListCommand {
@@ -194,6 +274,7 @@ pub struct ShuffleListCommand {
 
 impl Command for ShuffleListCommand {
     fn apply(&mut self, context: &mut Context) {
+        self.bookmark.select(context.board);
         context.board.lists.swap(self.from_index, self.to_index);
         context.board.current_list = Some(self.to_index);
     }
@@ -203,6 +284,78 @@ impl Command for ShuffleListCommand {
     }
 }
 
+pub struct ResizeListCommand {
+    pub list: usize,
+    pub old: Option<u16>,
+    pub new: Option<u16>,
+    pub bookmark: SelectionBookmark,
+}
+
+impl Command for ResizeListCommand {
+    fn apply(&mut self, context: &mut Context) {
+        context.board.lists[self.list].width_override = self.new;
+        self.bookmark.select(context.board);
+    }
+    fn revert(&mut self, context: &mut Context) {
+        context.board.lists[self.list].width_override = self.old;
+        self.bookmark.select(context.board);
+    }
+}
+
+pub struct CollapseListCommand {
+    pub lists: Vec<usize>,
+    pub bookmark: SelectionBookmark,
+}
+
+impl Command for CollapseListCommand {
+    fn apply(&mut self, context: &mut Context) {
+        for list in self.lists.iter() {
+            let list = &mut context.board.lists[*list];
+            list.collapsed = !list.collapsed;
+        }
+        self.bookmark.select(context.board);
+    }
+    fn revert(&mut self, context: &mut Context) {
+        self.apply(context);
+    }
+}
+
+pub struct SortListCommand {
+    pub list: usize,
+    pub order: Vec<usize>,
+    pub bookmark: SelectionBookmark,
+}
+
+impl SortListCommand {
+    fn select_moved_item(&self, board: &mut Board, position: impl Fn(usize) -> usize) {
+        self.bookmark.select(board);
+        if let Some(item) = self.bookmark.item {
+            board.lists[self.list].set_selection_index(position(item));
+            board.lists[self.list].set_selection();
+        }
+    }
+}
+
+impl Command for SortListCommand {
+    fn apply(&mut self, context: &mut Context) {
+        let list = &mut context.board.lists[self.list];
+        let items = std::mem::take(&mut list.items);
+        list.items = self.order.iter().map(|&i| items[i].clone()).collect();
+        self.select_moved_item(context.board, |item| {
+            self.order.iter().position(|&i| i == item).unwrap_or(item)
+        });
+    }
+    fn revert(&mut self, context: &mut Context) {
+        let list = &mut context.board.lists[self.list];
+        let mut items = list.items.clone();
+        for (position, &i) in self.order.iter().enumerate() {
+            items[i] = list.items[position].clone();
+        }
+        list.items = items;
+        self.bookmark.select(context.board);
+    }
+}
+
 pub struct ShuffleItemCommand {
     pub list: usize,
     pub from_index: usize,
@@ -212,6 +365,7 @@ pub struct ShuffleItemCommand {
 
 impl Command for ShuffleItemCommand {
     fn apply(&mut self, context: &mut Context) {
+        self.bookmark.select(context.board);
         let current_list = &mut context.board.lists[self.list];
         current_list.items.swap(self.from_index, self.to_index);
         current_list.set_selection_index(self.to_index);
@@ -234,6 +388,7 @@ pub struct MoveItemCommand {
 
 impl Command for MoveItemCommand {
     fn apply(&mut self, context: &mut Context) {
+        self.bookmark.select(context.board);
         let current_list = &mut context.board.lists[self.from_list];
         if let Some(item) = current_list.items.get(self.from_index).cloned() {
             current_list.remove_item(self.from_index);
@@ -260,42 +415,67 @@ impl Command for MoveItemCommand {
         }
         self.bookmark.select(context.board);
     }
+    fn activity(&self, board: &Board) -> Option<String> {
+        Some(format!(
+            "moved {} from {} to {}",
+            item_label(board.lists[self.to_list].items.get(self.to_index)?),
+            board.lists[self.from_list].name,
+            board.lists[self.to_list].name
+        ))
+    }
 }
 
 pub struct ToggleItemCommand {
     pub list: usize,
     pub item: usize,
     pub bookmark: SelectionBookmark,
+    // the next occurrence of a recurring item being completed
+    pub spawn: Option<AddItemCommand>,
 }
 
 impl Command for ToggleItemCommand {
     fn apply(&mut self, context: &mut Context) {
         let current_list = &mut context.board.lists[self.list];
         current_list.items[self.item].toggle();
+        if let Some(spawn) = self.spawn.as_mut() {
+            spawn.apply(context);
+        }
         self.bookmark.select(context.board);
     }
     fn revert(&mut self, context: &mut Context) {
+        if let Some(spawn) = self.spawn.as_mut() {
+            spawn.revert(context);
+        }
         let current_list = &mut context.board.lists[self.list];
         current_list.items[self.item].toggle();
         self.bookmark.select(context.board);
     }
+    fn activity(&self, board: &Board) -> Option<String> {
+        let list = &board.lists[self.list];
+        let item = &list.items[self.item];
+        let action = if item.done { "completed" } else { "reopened" };
+        Some(format!("{} {} in {}", action, item_label(item), list.name))
+    }
 }
 
 pub struct YankItemCommand {
     pub list: usize,
     pub item: usize,
     pub value: BoardItem,
-    pub last_clipboard: Option<String>,
+    pub bookmark: SelectionBookmark,
+    pub last_clipboard: Option<BoardItem>,
 }
 
 impl Command for YankItemCommand {
     fn apply(&mut self, context: &mut Context) {
         self.value = context.board.lists[self.list].items[self.item].clone();
         self.last_clipboard = context.clipboard.clone();
-        context.clipboard = Some(self.value.text.clone());
+        context.clipboard = Some(self.value.copied());
+        self.bookmark.select(context.board);
     }
     fn revert(&mut self, context: &mut Context) {
         context.clipboard = self.last_clipboard.clone();
+        self.bookmark.select(context.board);
     }
 }
 
@@ -304,16 +484,20 @@ pub struct CutItemCommand {
     pub item: usize,
     pub value: BoardItem,
     pub bookmark: SelectionBookmark,
-    pub last_clipboard: Option<String>,
+    pub last_clipboard: Option<BoardItem>,
 }
 
 impl Command for CutItemCommand {
     fn apply(&mut self, context: &mut Context) {
         self.value = context.board.lists[self.list].items.remove(self.item);
+        self.bookmark.select(context.board);
         context.board.lists[self.list].set_selection_index(self.item.saturating_sub(1));
         context.board.lists[self.list].set_selection();
         self.last_clipboard = context.clipboard.clone();
-        context.clipboard = Some(self.value.text.clone());
+        context.clipboard = Some(BoardItem {
+            board: None,
+            ..self.value.clone()
+        });
     }
     fn revert(&mut self, context: &mut Context) {
         context.board.lists[self.list]
@@ -322,24 +506,228 @@ impl Command for CutItemCommand {
         self.bookmark.select(context.board);
         context.clipboard = self.last_clipboard.clone();
     }
+    fn activity(&self, board: &Board) -> Option<String> {
+        Some(format!(
+            "cut {} from {}",
+            item_label(&self.value),
+            board.lists[self.list].name
+        ))
+    }
 }
 
 pub struct PasteItemCommand {
     pub list: usize,
     pub item: usize,
     pub bookmark: SelectionBookmark,
+    pub last_clipboard: Option<BoardItem>,
 }
 
 impl Command for PasteItemCommand {
+    // A cut item keeps its identity on the first paste only, later pastes
+    // are copies
     fn apply(&mut self, context: &mut Context) {
-        context.board.lists[self.list].items.insert(
-            self.item,
-            BoardItem::new(&context.clipboard.as_ref().unwrap().clone()),
-        );
+        self.last_clipboard = context.clipboard.clone();
+        context.clipboard = self.last_clipboard.as_ref().map(BoardItem::copied);
+        context.board.lists[self.list]
+            .items
+            .insert(self.item, self.last_clipboard.clone().unwrap_or_default());
         self.bookmark.select(context.board);
     }
     fn revert(&mut self, context: &mut Context) {
         context.board.lists[self.list].items.remove(self.item);
+        context.clipboard = self.last_clipboard.clone();
         self.bookmark.select_with_offset(context.board, -1);
     }
+    fn activity(&self, board: &Board) -> Option<String> {
+        let list = &board.lists[self.list];
+        Some(format!(
+            "pasted {} into {}",
+            item_label(list.items.get(self.item)?),
+            list.name
+        ))
+    }
+}
+
+/// Board commands applied together as a single undoable step.
+pub struct CompositeCommand {
+    pub commands: Vec<(usize, Box<dyn Command>)>,
+}
+
+impl TreeCommand for CompositeCommand {
+    fn apply(&mut self, context: &mut TreeContext) {
+        for (board, command) in self.commands.iter_mut() {
+            command.apply(&mut Context {
+                board: &mut context.boards[*board],
+                clipboard: None,
+            });
+        }
+    }
+    fn revert(&mut self, context: &mut TreeContext) {
+        for (board, command) in self.commands.iter_mut().rev() {
+            command.revert(&mut Context {
+                board: &mut context.boards[*board],
+                clipboard: None,
+            });
+        }
+    }
+}
+
+/// Moves an item, with its sub-board, into a list on any board.
+pub struct MoveToBoardCommand {
+    pub board: usize,
+    pub list: usize,
+    pub item: usize,
+    pub to_board: usize,
+    pub to_list: usize,
+    pub to_item: usize,
+    // what moved where, as written to the activity log
+    pub description: String,
+    pub bookmark: SelectionBookmark,
+}
+
+impl TreeCommand for MoveToBoardCommand {
+    fn apply(&mut self, context: &mut TreeContext) {
+        let item = context.boards[self.board].lists[self.list]
+            .items
+            .remove(self.item);
+        context.boards[self.to_board].lists[self.to_list]
+            .items
+            .insert(self.to_item, item);
+        let board = &mut context.boards[self.board];
+        self.bookmark.select(board);
+        board.lists[self.list].set_selection_index(self.item.saturating_sub(1));
+        board.lists[self.list].set_selection();
+    }
+    fn revert(&mut self, context: &mut TreeContext) {
+        let item = context.boards[self.to_board].lists[self.to_list]
+            .items
+            .remove(self.to_item);
+        context.boards[self.board].lists[self.list]
+            .items
+            .insert(self.item, item);
+        self.bookmark.select(&mut context.boards[self.board]);
+    }
+    fn activity(&self) -> Option<String> {
+        Some(format!("moved {}", self.description))
+    }
+}
+
+/// Moves an item into the sub-board of the item above it, giving that
+/// item a sub-board with a list when it has none.
+pub struct DemoteItemCommand {
+    pub board: usize,
+    pub list: usize,
+    pub item: usize,
+    pub sub_board: usize,
+    // whether the sub-board was created for this move
+    pub created_board: bool,
+    // name of the list added to a sub-board without lists
+    pub new_list: Option<String>,
+    pub description: String,
+    pub bookmark: SelectionBookmark,
+}
+
+impl TreeCommand for DemoteItemCommand {
+    fn apply(&mut self, context: &mut TreeContext) {
+        let items = &mut context.boards[self.board].lists[self.list].items;
+        let item = items.remove(self.item);
+        items[self.item - 1].board = Some(self.sub_board);
+        let sub_board = &mut context.boards[self.sub_board];
+        if let Some(name) = &self.new_list {
+            sub_board.lists.push(BoardList {
+                name: name.clone(),
+                ..Default::default()
+            });
+        }
+        sub_board.lists[0].items.push(item);
+        let board = &mut context.boards[self.board];
+        self.bookmark.select(board);
+        board.lists[self.list].set_selection_index(self.item - 1);
+        board.lists[self.list].set_selection();
+    }
+    fn revert(&mut self, context: &mut TreeContext) {
+        let sub_board = &mut context.boards[self.sub_board];
+        let item = sub_board.lists[0].items.pop().unwrap_or_default();
+        if self.new_list.is_some() {
+            sub_board.lists.pop();
+        }
+        let items = &mut context.boards[self.board].lists[self.list].items;
+        if self.created_board {
+            items[self.item - 1].board = None;
+        }
+        items.insert(self.item, item);
+        self.bookmark.select(&mut context.boards[self.board]);
+    }
+    fn activity(&self) -> Option<String> {
+        Some(format!("demoted {}", self.description))
+    }
+}
+
+pub struct ArchivedItem {
+    pub board: usize,
+    pub list: usize,
+    pub item: usize,
+    pub value: BoardItem,
+}
+
+pub struct ArchiveCommand {
+    // grouped by source list with ascending item indexes, so they can be
+    // removed back to front and reinserted front to back
+    pub items: Vec<ArchivedItem>,
+    pub path: PathBuf,
+    pub before: Option<String>,
+    pub after: String,
+    pub bookmark: SelectionBookmark,
+    pub warning: Option<String>,
+}
+
+impl ArchiveCommand {
+    fn write_archive(&mut self, contents: Option<String>) {
+        let result = match contents {
+            Some(contents) => fs::write(&self.path, contents),
+            None => fs::remove_file(&self.path).or_else(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Ok(()),
+                _ => Err(e),
+            }),
+        };
+        if let Err(e) = result {
+            self.warning = Some(format!(
+                "Unable to update archive {}: {}",
+                self.path.display(),
+                e
+            ));
+        }
+    }
+}
+
+impl TreeCommand for ArchiveCommand {
+    fn apply(&mut self, context: &mut TreeContext) {
+        for a in self.items.iter().rev() {
+            context.boards[a.board].lists[a.list].items.remove(a.item);
+        }
+        self.write_archive(Some(self.after.clone()));
+        let board = &mut context.boards[context.board];
+        self.bookmark.select(board);
+        if let (Some(list), Some(item)) = (self.bookmark.list, self.bookmark.item)
+            && let Some(list) = board.lists.get_mut(list)
+        {
+            list.set_selection_index(item);
+            list.set_selection();
+        }
+    }
+    fn revert(&mut self, context: &mut TreeContext) {
+        for a in self.items.iter() {
+            context.boards[a.board].lists[a.list]
+                .items
+                .insert(a.item, a.value.clone());
+        }
+        self.write_archive(self.before.clone());
+        self.bookmark.select(&mut context.boards[context.board]);
+    }
+    fn activity(&self) -> Option<String> {
+        Some(format!("archived {} done items", self.items.len()))
+    }
+    fn take_warning(&mut self) -> Option<String> {
+        self.warning.take()
+    }
 }
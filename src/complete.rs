@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use ratatui_textarea::{DataCursor, TextArea};
+
+use crate::App;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The tag being typed right before the cursor, without its hash sign.
+pub fn typed_tag(textarea: &TextArea) -> Option<String> {
+    let DataCursor(row, col) = textarea.cursor();
+    let before = textarea
+        .lines()
+        .get(row)?
+        .chars()
+        .take(col)
+        .collect::<String>();
+    let word = before.rsplit(char::is_whitespace).next()?;
+    word.rsplit_once('#').map(|(_, tag)| tag.to_string())
+}
+
+/// Replaces the tag being typed with a complete one.
+pub fn complete_tag(textarea: &mut TextArea, tag: &str) {
+    if let Some(typed) = typed_tag(textarea) {
+        for _ in 0..typed.chars().count() {
+            textarea.delete_char();
+        }
+        textarea.insert_str(tag);
+    }
+}
+
+impl App {
+    /// Tags of every item in the tree, most used first.
+    pub fn ranked_tags(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for item in self.tree_items(0) {
+            for tag in item.tags() {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        let mut tags = counts
+            .into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect::<Vec<_>>();
+        tags.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        tags
+    }
+
+    /// Known tags completing the one being typed in the given textarea.
+    pub fn tag_suggestions(&self, textarea: &TextArea) -> Vec<(String, usize)> {
+        let Some(typed) = typed_tag(textarea) else {
+            return Vec::new();
+        };
+        let typed = typed.to_lowercase();
+        self.ranked_tags()
+            .into_iter()
+            .filter(|(tag, _)| {
+                tag.to_lowercase().starts_with(&typed) && tag.to_lowercase() != typed
+            })
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    }
+
+    /// Completes the tag typed in the item editor, returning false when
+    /// there is nothing to complete.
+    pub fn complete_tag_in_editor(&mut self) -> bool {
+        let Some(textarea) = &self.edit_textarea else {
+            return false;
+        };
+        let Some((tag, _)) = self.tag_suggestions(textarea).into_iter().next() else {
+            return false;
+        };
+        if let Some(textarea) = self.edit_textarea.as_mut() {
+            complete_tag(textarea, &tag);
+        }
+        true
+    }
+
+    /// Completes the tag typed in the search bar, returning the new query.
+    pub fn complete_tag_in_search(&mut self) -> Option<String> {
+        let (tag, _) = self
+            .tag_suggestions(self.search.textarea())
+            .into_iter()
+            .next()?;
+        Some(self.search.complete_tag(&tag))
+    }
+}
+
+/// Draws the suggestions next to a line of text, below it when there is
+/// room and above it otherwise. The first suggestion is the one Tab picks.
+pub fn draw_tag_suggestions(frame: &mut Frame, line: Rect, suggestions: &[(String, usize)]) {
+    if suggestions.is_empty() {
+        return;
+    }
+    let width = suggestions
+        .iter()
+        .map(|(tag, count)| tag.chars().count() + count.to_string().len() + 4)
+        .max()
+        .unwrap_or_default() as u16
+        + 2;
+    let height = suggestions.len() as u16 + 2;
+    let frame_area = frame.area();
+    let y = if line.y + line.height + height <= frame_area.height {
+        line.y + line.height
+    } else {
+        line.y.saturating_sub(height)
+    };
+    let x = line.x.min(frame_area.width.saturating_sub(width));
+    let area = Rect::new(x, y, width, height).intersection(frame_area);
+
+    let lines = suggestions
+        .iter()
+        .enumerate()
+        .map(|(i, (tag, count))| {
+            let line = Line::from(vec![
+                Span::raw(format!("#{}", tag)),
+                Span::raw(format!(" {}", count)).dark_gray(),
+            ]);
+            if i == 0 { line.reversed() } else { line }
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::new().dark_gray()),
+        ),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui_textarea::CursorMove;
+
+    #[test]
+    fn test_typed_tag() {
+        let mut textarea = TextArea::new(vec!["Fix #sea and more".to_string()]);
+        assert_eq!(typed_tag(&textarea), None);
+        textarea.move_cursor(CursorMove::Jump(0, 8));
+        assert_eq!(typed_tag(&textarea).as_deref(), Some("sea"));
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        assert_eq!(typed_tag(&textarea).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_complete_tag() {
+        let mut textarea = TextArea::new(vec!["Fix #Sea".to_string()]);
+        textarea.move_cursor(CursorMove::End);
+        complete_tag(&mut textarea, "search");
+        assert_eq!(textarea.lines(), ["Fix #search"]);
+    }
+}
@@ -1,14 +1,82 @@
 use ratatui::style::Color;
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+pub type LinkTargets = BTreeMap<String, (String, bool)>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BoardConfig {
     #[serde(default)]
     pub dim_tailing_items: bool,
+    #[serde(default = "default_focus_items")]
+    pub focus_items: usize,
+    #[serde(default)]
+    pub focus_exempt_lists: Vec<String>,
+    #[serde(default)]
+    pub enforce_wip_limits: bool,
+    #[serde(default)]
+    pub property_chips: Vec<String>,
+    #[serde(default)]
+    pub recurring_list: String,
     #[serde(default)]
     pub path_separator: PathSeparator,
+    #[serde(default = "default_activity_log")]
+    pub activity_log: bool,
+    #[serde(default, rename = "Columns")]
+    pub columns: Columns,
     #[serde(default, rename = "Styles")]
     pub styles: Styles,
+    // copied from the top-level [Tags] section by Config::board_config
+    #[serde(skip)]
+    pub tag_styles: BTreeMap<String, Style>,
+    // text and done state of every item with an id, refreshed after commands
+    #[serde(skip)]
+    pub link_targets: LinkTargets,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Columns {
+    pub min_width: u16,
+    pub max_width: u16,
+    pub fit_to_screen: bool,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Columns {
+            min_width: 20,
+            max_width: 50,
+            fit_to_screen: false,
+        }
+    }
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        BoardConfig {
+            dim_tailing_items: false,
+            focus_items: default_focus_items(),
+            focus_exempt_lists: Vec::new(),
+            enforce_wip_limits: false,
+            property_chips: Vec::new(),
+            recurring_list: String::new(),
+            path_separator: PathSeparator::default(),
+            activity_log: default_activity_log(),
+            columns: Columns::default(),
+            styles: Styles::default(),
+            tag_styles: BTreeMap::new(),
+            link_targets: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_focus_items() -> usize {
+    1
+}
+
+fn default_activity_log() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -23,15 +91,38 @@ impl Default for PathSeparator {
 pub struct Config {
     #[serde(rename = "Board")]
     pub board_config: BoardConfig,
+    #[serde(default, rename = "Tags")]
+    pub tags: BTreeMap<String, Style>,
+}
+
+impl Config {
+    /// The configuration handed to boards, including the tag colours.
+    pub fn board_config(&self) -> BoardConfig {
+        BoardConfig {
+            tag_styles: self.tags.clone(),
+            ..self.board_config.clone()
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct Styles {
     pub header: Style,
     pub active_header: Style,
+    pub wip_exceeded: Style,
     pub item: Style,
     pub tag: Style,
     pub tag_hashsign: Style,
+    pub mention: Style,
+    pub due_upcoming: Style,
+    pub due_overdue: Style,
+    pub priority_high: Style,
+    pub priority_medium: Style,
+    pub priority_low: Style,
+    pub property_chip: Style,
+    pub link: Style,
+    pub blocked: Style,
     pub fringe_on: Style,
     pub fringe_off: Style,
     pub selected: Style,
@@ -42,9 +133,31 @@ impl Default for Styles {
         Styles {
             header: Style::with_fg(Color::White),
             active_header: Style::with_fg(Color::White),
+            wip_exceeded: Style::with_fg(Color::LightRed),
             item: Style::default(),
             tag: Style::with_fg(Color::Yellow),
             tag_hashsign: Style::with_fg(Color::DarkGray),
+            mention: Style::with_fg(Color::LightMagenta),
+            due_upcoming: Style::with_fg(Color::Cyan),
+            due_overdue: Style::with_fg(Color::LightRed),
+            priority_high: Style {
+                fg: Color::Black,
+                bg: Color::LightRed,
+            },
+            priority_medium: Style {
+                fg: Color::Black,
+                bg: Color::Yellow,
+            },
+            priority_low: Style {
+                fg: Color::Black,
+                bg: Color::Gray,
+            },
+            link: Style::with_fg(Color::LightCyan),
+            blocked: Style::with_fg(Color::DarkGray),
+            property_chip: Style {
+                fg: Color::Gray,
+                bg: Color::Indexed(237),
+            },
             fringe_on: Style::with_fg(Color::LightBlue),
             fringe_off: Style::with_fg(Color::Indexed(239)),
             selected: Style {
@@ -56,6 +169,7 @@ impl Default for Styles {
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct Style {
     #[serde(with = "color_serde")]
     pub fg: Color,
@@ -95,7 +209,7 @@ fn str_to_color(color_as_str: &str) -> Option<Color> {
         }
         let hex = if hex.len() == 3 {
             hex.chars()
-                .flat_map(|c| std::iter::repeat(c).take(2))
+                .flat_map(|c| std::iter::repeat_n(c, 2))
                 .collect::<String>()
         } else {
             hex.to_string()
@@ -0,0 +1,232 @@
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, Weekday};
+
+pub fn today() -> Date {
+    now().date()
+}
+
+pub fn now() -> PrimitiveDateTime {
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    PrimitiveDateTime::new(now.date(), now.time())
+}
+
+/// Parses an ISO date (`2026-11-01`) or one of the relative forms: `today`,
+/// `tomorrow`, `yesterday`, a weekday name (`mon`, `friday`) for its next
+/// occurrence, or an offset in days or weeks (`3d`, `2w`).
+pub fn parse(value: &str, today: Date) -> Option<Date> {
+    match value.to_lowercase().as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.next_day(),
+        "yesterday" => today.previous_day(),
+        value => weekday(value)
+            .map(|w| today.next_occurrence(w))
+            .or_else(|| offset(value).and_then(|d| today.checked_add(d)))
+            .or_else(|| parse_iso(value)),
+    }
+}
+
+pub fn parse_iso(value: &str) -> Option<Date> {
+    let mut parts = value.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+    Date::from_calendar_date(year.parse().ok()?, month, day.parse().ok()?).ok()
+}
+
+pub fn format(date: Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        date.month() as u8,
+        date.day()
+    )
+}
+
+/// Formats a moment to the minute, e.g. `2026-10-19 09:05`.
+pub fn format_time(time: PrimitiveDateTime) -> String {
+    format!(
+        "{} {:02}:{:02}",
+        format(time.date()),
+        time.hour(),
+        time.minute()
+    )
+}
+
+pub fn parse_time(value: &str) -> Option<PrimitiveDateTime> {
+    let (date, time) = value.trim().split_once(' ')?;
+    let (hour, minute) = time.split_once(':')?;
+    let time = Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()?;
+    Some(PrimitiveDateTime::new(parse_iso(date)?, time))
+}
+
+/// Formats a duration in hours and minutes, e.g. `1h 5m`, `2h` or `40m`.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.whole_minutes().max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Formats a duration like `format_duration`, keeping the leftover seconds,
+/// e.g. `1h 5m 30s`, so that stored time doesn't lose them.
+pub fn format_exact_duration(duration: Duration) -> String {
+    let seconds = duration.whole_seconds().max(0) % 60;
+    match (duration.whole_minutes(), seconds) {
+        (_, 0) => format_duration(duration),
+        (..1, seconds) => format!("{}s", seconds),
+        (_, seconds) => format!("{} {}s", format_duration(duration), seconds),
+    }
+}
+
+/// Parses hours, minutes and seconds such as `1h 5m` or `40m 12s`, `None`
+/// when a part isn't one of them or the total doesn't fit in a duration.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    value
+        .split_whitespace()
+        .try_fold(Duration::ZERO, |total, part| {
+            let (count, unit) = if let Some(count) = part.strip_suffix('h') {
+                (count, 3_600)
+            } else if let Some(count) = part.strip_suffix('m') {
+                (count, 60)
+            } else {
+                (part.strip_suffix('s')?, 1)
+            };
+            let seconds = count.parse::<i64>().ok()?.checked_mul(unit)?;
+            total.checked_add(Duration::seconds(seconds))
+        })
+}
+
+pub fn weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "mon" | "monday" => Some(Weekday::Monday),
+        "tue" | "tuesday" => Some(Weekday::Tuesday),
+        "wed" | "wednesday" => Some(Weekday::Wednesday),
+        "thu" | "thursday" => Some(Weekday::Thursday),
+        "fri" | "friday" => Some(Weekday::Friday),
+        "sat" | "saturday" => Some(Weekday::Saturday),
+        "sun" | "sunday" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+/// The date following `from` in a recurrence: `day`, `week`, `month`,
+/// `year`, a weekday name (`mon`, `friday`) or an interval such as `3d`/`2w`.
+pub fn next_occurrence(every: &str, from: Date) -> Option<Date> {
+    match every.to_lowercase().as_str() {
+        "day" | "daily" => from.next_day(),
+        "week" | "weekly" => from.checked_add(Duration::weeks(1)),
+        "month" | "monthly" => add_months(from, 1),
+        "year" | "yearly" => add_months(from, 12),
+        value => weekday(value)
+            .map(|w| from.next_occurrence(w))
+            .or_else(|| offset(value).and_then(|d| from.checked_add(d))),
+    }
+}
+
+// Keeps the day of month where possible, e.g. Jan 31st is followed by Feb 28th
+fn add_months(date: Date, months: i32) -> Option<Date> {
+    let index = date.year() * 12 + date.month() as i32 - 1 + months;
+    let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u8 + 1);
+    let month = Month::try_from(month).ok()?;
+    Date::from_calendar_date(year, month, date.day().min(month.length(year))).ok()
+}
+
+// A count of days or weeks, `None` when it doesn't fit in a duration
+fn offset(value: &str) -> Option<Duration> {
+    let (count, unit) = if let Some(count) = value.strip_suffix('d') {
+        (count, 86_400)
+    } else {
+        (value.strip_suffix('w')?, 604_800)
+    };
+    let seconds = count.parse::<i64>().ok()?.checked_mul(unit)?;
+    Some(Duration::seconds(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_parse_iso() {
+        assert_eq!(parse_iso("2026-11-01"), Some(date!(2026 - 11 - 01)));
+        assert_eq!(parse_iso("2026-13-01"), None);
+        assert_eq!(parse_iso("2026-1-01"), None);
+        assert_eq!(parse_iso("tomorrow"), None);
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        let today = date!(2026 - 10 - 19);
+        assert_eq!(parse("today", today), Some(today));
+        assert_eq!(parse("Tomorrow", today), Some(date!(2026 - 10 - 20)));
+        assert_eq!(parse("yesterday", today), Some(date!(2026 - 10 - 18)));
+        assert_eq!(parse("mon", today), Some(date!(2026 - 10 - 26)));
+        assert_eq!(parse("friday", today), Some(date!(2026 - 10 - 23)));
+        assert_eq!(parse("3d", today), Some(date!(2026 - 10 - 22)));
+        assert_eq!(parse("2w", today), Some(date!(2026 - 11 - 02)));
+        assert_eq!(parse("soon", today), None);
+        assert_eq!(parse("café", today), None);
+        assert_eq!(parse("999999999999999d", today), None);
+        assert_eq!(parse("99999999999999999w", today), None);
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let from = date!(2026 - 10 - 19);
+        assert_eq!(next_occurrence("day", from), Some(date!(2026 - 10 - 20)));
+        assert_eq!(next_occurrence("Week", from), Some(date!(2026 - 10 - 26)));
+        assert_eq!(next_occurrence("mon", from), Some(date!(2026 - 10 - 26)));
+        assert_eq!(next_occurrence("fri", from), Some(date!(2026 - 10 - 23)));
+        assert_eq!(next_occurrence("10d", from), Some(date!(2026 - 10 - 29)));
+        assert_eq!(next_occurrence("year", from), Some(date!(2027 - 10 - 19)));
+        assert_eq!(
+            next_occurrence("month", date!(2026 - 12 - 31)),
+            Some(date!(2027 - 01 - 31))
+        );
+        assert_eq!(
+            next_occurrence("monthly", date!(2027 - 01 - 31)),
+            Some(date!(2027 - 02 - 28))
+        );
+        assert_eq!(next_occurrence("sometimes", from), None);
+        assert_eq!(next_occurrence("é", from), None);
+        assert_eq!(next_occurrence("999999999999999d", from), None);
+    }
+
+    #[test]
+    fn test_times_and_durations() {
+        let time = parse_time("2026-10-19 09:05").unwrap();
+        assert_eq!(time, time::macros::datetime!(2026 - 10 - 19 09:05));
+        assert_eq!(format_time(time), "2026-10-19 09:05");
+        assert_eq!(parse_time("2026-10-19"), None);
+        assert_eq!(parse_time("2026-10-19 25:00"), None);
+
+        assert_eq!(format_duration(Duration::minutes(65)), "1h 5m");
+        assert_eq!(format_duration(Duration::minutes(120)), "2h");
+        assert_eq!(format_duration(Duration::seconds(59)), "0m");
+        assert_eq!(parse_duration("1h 5m"), Some(Duration::minutes(65)));
+        assert_eq!(parse_duration("40m"), Some(Duration::minutes(40)));
+        assert_eq!(parse_duration("a while"), None);
+        assert_eq!(parse_duration("2½"), None);
+        assert_eq!(parse_duration("9999999999999999h"), None);
+        assert_eq!(
+            parse_duration("9223372036854775807s 1s"),
+            None,
+            "the total overflows"
+        );
+
+        assert_eq!(format_exact_duration(Duration::seconds(3_930)), "1h 5m 30s");
+        assert_eq!(format_exact_duration(Duration::seconds(45)), "45s");
+        assert_eq!(format_exact_duration(Duration::minutes(40)), "40m");
+        assert_eq!(parse_duration("1h 5m 30s"), Some(Duration::seconds(3_930)));
+        assert_eq!(parse_duration("45s"), Some(Duration::seconds(45)));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format(date!(2026 - 01 - 05)), "2026-01-05");
+    }
+}
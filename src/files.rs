@@ -0,0 +1,208 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::{App, Board, list::FILE_PROPERTY};
+
+// Lowercase words of the item title joined by dashes, for file names
+fn slug(title: &str) -> String {
+    let slug = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "board".to_string()
+    } else {
+        slug
+    }
+}
+
+impl App {
+    /// Where a linked file is, relative to the directory of the main file.
+    /// Only plain relative names are followed, so a board can't reach files
+    /// outside its directory with an absolute path or `..`.
+    pub fn linked_file_path(&self, file: &str) -> Option<PathBuf> {
+        let relative = Path::new(file);
+        let plain = relative.components().next().is_some()
+            && relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        plain.then(|| {
+            self.filename
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(relative)
+        })
+    }
+
+    /// The loaded sub-boards saved to their linked files, with the paths
+    /// and the titles of their items. A file is only written for the first
+    /// sub-board naming it and never over the main file; the other
+    /// sub-boards are saved inline.
+    pub fn linked_boards(&self) -> Vec<(usize, PathBuf, &str)> {
+        let mut linked: Vec<(usize, PathBuf, &str)> = Vec::new();
+        for item in self.tree_items(0) {
+            if let (Some(file), Some(sub_board)) = (item.linked_file(), item.board)
+                && let Some(path) = self.linked_file_path(file)
+                && path != self.filename
+                && !linked.iter().any(|(_, p, _)| *p == path)
+            {
+                linked.push((sub_board, path, item.title()));
+            }
+        }
+        linked
+    }
+
+    // Loads a linked file into a new board, unless it is outside the board
+    // directory, isn't a board, or is already part of the tree, which would
+    // otherwise load it again and again when files link to each other
+    fn load_linked(&mut self, file: &str) -> Result<usize, String> {
+        let path = self
+            .linked_file_path(file)
+            .ok_or_else(|| format!("Linked file {} is outside the board directory", file))?;
+        if !path.exists() {
+            return Err(format!("Linked file {} not found", path.display()));
+        }
+        if path == self.filename || self.linked_boards().iter().any(|(_, p, _)| *p == path) {
+            return Err(format!(
+                "Linked file {} is already part of the board",
+                path.display()
+            ));
+        }
+        self.boards
+            .push(Board::default().with_config(self.config.board_config()));
+        let board = self.boards.len() - 1;
+        if let Err(e) = self.read_md(&path, board) {
+            self.boards.pop();
+            return Err(format!("Linked file {} not loaded: {}", path.display(), e));
+        }
+        Ok(board)
+    }
+
+    // An unused file name next to the main file, named after the item
+    fn new_linked_file(&self, title: &str) -> String {
+        let stem = self
+            .filename
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = format!("{}-{}", stem, slug(title));
+        (1..)
+            .map(|n| match n {
+                1 => format!("{}.md", name),
+                n => format!("{}-{}.md", name, n),
+            })
+            .find(|file| {
+                self.linked_file_path(file)
+                    .is_some_and(|path| !path.exists())
+                    && !self
+                        .tree_items(0)
+                        .iter()
+                        .any(|item| item.linked_file() == Some(file))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Loads the sub-board of the selected item from its linked file, the
+    /// first time the item is opened. Returns the loaded board.
+    pub fn load_linked_board(&mut self) -> Option<usize> {
+        let item = self.active_board().current_list()?.current_item()?;
+        if item.board.is_some() {
+            return item.board;
+        }
+        let file = item.linked_file()?.to_string();
+        let board = match self.load_linked(&file) {
+            Ok(board) => board,
+            Err(e) => {
+                self.warning = Some(e);
+                return None;
+            }
+        };
+        self.active_board_mut()
+            .current_list_mut()?
+            .current_item_mut()?
+            .board = Some(board);
+        self.refresh_link_targets();
+        Some(board)
+    }
+
+    /// Loads every linked file of the tree not opened yet, including those
+    /// linked from the loaded ones, so tree-wide changes reach them too.
+    /// Missing files are left for the warning shown when opening them, and
+    /// files that can't be loaded are warned about and left alone.
+    pub fn load_linked_files(&mut self) {
+        loop {
+            let unloaded: Vec<_> = self
+                .tree_boards(0)
+                .into_iter()
+                .flat_map(|board| {
+                    self.boards[board]
+                        .lists
+                        .iter()
+                        .enumerate()
+                        .flat_map(move |(l, list)| {
+                            list.items.iter().enumerate().filter_map(move |(i, item)| {
+                                let file = item.linked_file().filter(|_| item.board.is_none())?;
+                                Some((board, l, i, file.to_string()))
+                            })
+                        })
+                })
+                .filter(|(.., file)| self.linked_file_path(file).is_none_or(|path| path.exists()))
+                .collect();
+            let mut loaded = false;
+            for (board, list, item, file) in unloaded {
+                match self.load_linked(&file) {
+                    Ok(sub_board) => {
+                        self.boards[board].lists[list].items[item].board = Some(sub_board);
+                        loaded = true;
+                    }
+                    Err(e) => self.warning = Some(e),
+                }
+            }
+            if !loaded {
+                break;
+            }
+        }
+        self.refresh_link_targets();
+    }
+
+    /// Moves the sub-board of the selected item to a file of its own, or
+    /// inlines a linked file back into the main file.
+    pub fn toggle_linked_file(&mut self) {
+        let Some(item) = self
+            .active_board()
+            .current_list()
+            .and_then(|list| list.current_item())
+        else {
+            return;
+        };
+        let mut properties = item.properties.clone();
+        if item.linked_file().is_some() {
+            if self.load_linked_board().is_none() {
+                return;
+            }
+            properties.remove(FILE_PROPERTY);
+        } else if item.board.is_some() {
+            properties.insert(
+                FILE_PROPERTY.to_string(),
+                self.new_linked_file(item.title()),
+            );
+        } else {
+            self.warning = Some("The selected item has no sub-board".to_string());
+            return;
+        }
+        if let Some(cmd) = self
+            .active_board()
+            .change_current_item_properties(properties)
+        {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    /// Writes every loaded sub-board that lives in a linked file.
+    pub fn write_linked_files(&self) {
+        for (sub_board, path, title) in self.linked_boards() {
+            self.write_board_md(&path, sub_board, title);
+        }
+    }
+}
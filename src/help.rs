@@ -1,53 +1,62 @@
-use ratatui::{
-    layout::{Alignment, Constraint, Flex, Layout, Rect},
-    style::{Color, Style, Stylize},
-    widgets::{Block, Borders, Clear, Padding, Paragraph},
-    Frame,
-};
+use ratatui::Frame;
 
-pub fn show_help_popup(frame: &mut Frame) {
-    let area = frame.area();
-
-    fn center_horizontal(area: Rect, width: u16) -> Rect {
-        let [area] = Layout::horizontal([Constraint::Length(width)])
-            .flex(Flex::Center)
-            .areas(area);
-        area
-    }
-    fn center_vertical(area: Rect, height: u16) -> Rect {
-        let [area] = Layout::vertical([Constraint::Length(height)])
-            .flex(Flex::Center)
-            .areas(area);
-        area
-    }
-    let area = center_vertical(center_horizontal(area, 60), 32);
-
-    let block = Block::default()
-        .title("Help")
-        .padding(Padding::new(2, 2, 1, 1))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::White).bg(Color::Black));
+use crate::popup::{key_lines, show_popup};
 
+pub fn show_help_popup(frame: &mut Frame) {
     let keybindings = vec![
         ("o", "Add new item to the current list"),
         ("d", "Delete selected item"),
         ("Enter", "Edit current item"),
         ("Ctrl-o", "Add new list to the current board"),
-        ("Ctrl-d", "Delete the current list"),
+        ("Ctrl-x", "Delete the current list"),
         ("Space", "Toggle current item strikethrough"),
         ("/", "Search for items"),
         ("Down or j", "Move down"),
         ("Up or k", "Move up"),
         ("Right or l", "Move right"),
         ("Left or h", "Move left"),
+        ("PgDn or PgUp", "Move a page down or up"),
+        ("Ctrl-d/Ctrl-u", "Move half a page down or up"),
+        ("J or K", "Move half a page down or up"),
+        ("g or G", "Move to the top or bottom"),
         ("Ctrl-h", "Move item to previous list"),
         ("Ctrl-l", "Move item to next list"),
+        ("Shift-m", "Move item to any list of the tree"),
+        ("Shift-p", "Move item up next to the parent-board item"),
+        ("Shift-d", "Move item into the sub-board of the item above"),
         ("Ctrl-j", "Deprioritize selected item"),
         ("Ctrl-k", "Prioritize selected item"),
         ("Shift-h", "Shuffle list right"),
         ("Shift-l", "Shuffle list left"),
+        ("s", "Sort the current list"),
+        ("!", "Cycle the selected item priority"),
+        ("m", "Edit the selected item properties"),
+        ("@", "Filter by the selected item assignee"),
+        ("w", "Show open items per assignee"),
+        ("t", "Manage tags"),
+        ("i", "Start or stop the selected item timer"),
+        ("Shift-i", "Show tracked time per list and sub-board"),
+        ("v", "Show the activity log"),
+        ("Shift-r", "Show the flow report of the current board"),
+        ("b", "Copy a link to the selected item"),
+        ("n", "Follow the selected item link"),
+        ("a", "Archive done items of the current board"),
+        ("Shift-a", "Archive done items of the whole tree"),
+        ("f", "Toggle focus mode"),
+        ("c", "Collapse or expand the current list"),
+        ("Shift-c", "Expand all collapsed lists"),
+        (">", "Widen the current list"),
+        ("<", "Narrow the current list"),
+        ("=", "Reset the current list width"),
         ("Tab", "Navigate to the item child-board"),
+        (
+            "Shift-f",
+            "Move the item child-board to its own file or back",
+        ),
         ("Esc", "Go back to the parent-board"),
+        ("Shift-b", "Jump to any parent-board"),
+        ("Shift-t", "Jump to any board of the tree"),
+        ("Shift-o", "Show the whole tree as an outline"),
         ("y", "Yank selected item"),
         ("x", "Cut selected item"),
         ("p", "Paste item"),
@@ -55,35 +64,11 @@ pub fn show_help_popup(frame: &mut Frame) {
         ("r", "Redo action"),
         ("q", "Quit"),
     ];
-    let mut items: Vec<ratatui::text::Line> = keybindings
-        .iter()
-        .map(|(key, action)| {
-            ratatui::text::Line::from(vec![
-                ratatui::text::Span::raw(*key).bold().yellow(),
-                ratatui::text::Span::raw(" ".repeat(14 - key.len())),
-                ratatui::text::Span::styled(*action, Style::new()),
-            ])
-        })
-        .collect::<Vec<ratatui::text::Line>>();
-    items.insert(
-        0,
-        ratatui::text::Line::from(vec![
-            ratatui::text::Span::raw("key").bold().underlined(),
-            ratatui::text::Span::raw("           "),
-            ratatui::text::Span::raw("Action").bold().underlined(),
-        ]),
-    );
-
-    let content = Paragraph::new(items)
-        .style(Style::default().fg(Color::White).bg(Color::Black))
-        .block(block);
-
-    let footer = Paragraph::new("Press any key to close").dark_gray();
-
-    frame.render_widget(Clear, area);
-    frame.render_widget(content, area);
-    frame.render_widget(
-        footer.alignment(Alignment::Center),
-        Rect::new(area.x + 1, area.y + area.height - 2, area.width - 2, 1),
+    show_popup(
+        frame,
+        "Help",
+        60,
+        key_lines("Action", &keybindings),
+        "Press any key to close",
     );
 }
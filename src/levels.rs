@@ -0,0 +1,107 @@
+use crate::{
+    App, Board,
+    commands::{DemoteItemCommand, MoveToBoardCommand, item_label},
+};
+
+impl App {
+    /// Lifts the selected item out of the sub-board into the list of the
+    /// parent board, right after the item owning the sub-board.
+    pub fn promote_selected_item(&mut self) {
+        let Some(parent) = self.board_path.front().cloned() else {
+            self.warning = Some("Already on the top board".to_string());
+            return;
+        };
+        let (Some(to_list), Some(owner)) = (parent.source_list, parent.source_item) else {
+            return;
+        };
+        let board = self.active_board_index();
+        let Some(list) = self.active_board().current_list else {
+            return;
+        };
+        let Some(item) = self.active_board().lists[list].selected_item_index else {
+            return;
+        };
+        if self.refuses_items(parent.source_board, to_list) {
+            return;
+        }
+        let owner_list = &self.boards[parent.source_board].lists[to_list];
+        let description = format!(
+            "{} from {} to {}",
+            item_label(&self.active_board().lists[list].items[item]),
+            self.active_board().lists[list].name,
+            owner_list.name
+        );
+        self.apply_tree_command(Box::new(MoveToBoardCommand {
+            board,
+            list,
+            item,
+            to_board: parent.source_board,
+            to_list,
+            to_item: owner + 1,
+            description,
+            bookmark: self.active_board().get_selection_bookmark(),
+        }));
+    }
+
+    /// Pushes the selected item down into the sub-board of the item above
+    /// it, creating the sub-board when there is none.
+    pub fn demote_selected_item(&mut self) {
+        let board = self.active_board_index();
+        let Some(list) = self.active_board().current_list else {
+            return;
+        };
+        let Some(item) = self.active_board().lists[list].selected_item_index else {
+            return;
+        };
+        if item == 0 {
+            self.warning = Some("There is no item above to move this one under".to_string());
+            return;
+        }
+        let bookmark = self.active_board().get_selection_bookmark();
+        let owner = &self.active_board().lists[list].items[item - 1];
+        if owner.board.is_none() && owner.linked_file().is_some() {
+            self.active_board_mut().lists[list].set_selection_index(item - 1);
+            let loaded = self.load_linked_board();
+            bookmark.select(self.active_board_mut());
+            if loaded.is_none() {
+                return;
+            }
+        }
+        let owner = &self.boards[board].lists[list].items[item - 1];
+        if let Some(sub_board) = owner.board
+            && !self.boards[sub_board].lists.is_empty()
+            && self.refuses_items(sub_board, 0)
+        {
+            return;
+        }
+        let owner = &self.boards[board].lists[list].items[item - 1];
+        let (sub_board, created_board) = match owner.board {
+            Some(sub_board) => (sub_board, false),
+            None => {
+                self.boards
+                    .push(Board::default().with_config(self.config.board_config()));
+                (self.boards.len() - 1, true)
+            }
+        };
+        let items = &self.boards[board].lists[list].items;
+        let description = format!(
+            "{} under {}",
+            item_label(&items[item]),
+            item_label(&items[item - 1])
+        );
+        let new_list = self.boards[sub_board]
+            .lists
+            .is_empty()
+            .then(|| self.boards[board].lists[list].name.clone());
+        self.apply_tree_command(Box::new(DemoteItemCommand {
+            board,
+            list,
+            item,
+            sub_board,
+            created_board,
+            new_list,
+            description,
+            bookmark,
+        }));
+    }
+}
@@ -0,0 +1,117 @@
+use std::hash::{BuildHasher, RandomState};
+
+use crate::{App, commands::SelectionBookmark, config, list::ID_PROPERTY};
+
+const ID_LENGTH: usize = 6;
+const ID_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+impl App {
+    /// Where the item with the given id is, as board, list and item.
+    pub fn locate_item(&self, id: &str) -> Option<(usize, usize, usize)> {
+        self.tree_boards(0).into_iter().find_map(|board| {
+            self.boards[board]
+                .lists
+                .iter()
+                .enumerate()
+                .find_map(|(l, list)| {
+                    list.items
+                        .iter()
+                        .position(|item| item.id() == Some(id))
+                        .map(|i| (board, l, i))
+                })
+        })
+    }
+
+    fn new_item_id(&self) -> String {
+        let state = RandomState::new();
+        (0u64..)
+            .map(|attempt| {
+                let mut hash = state.hash_one(attempt);
+                (0..ID_LENGTH)
+                    .map(|_| {
+                        let c = ID_ALPHABET[(hash % ID_ALPHABET.len() as u64) as usize];
+                        hash /= ID_ALPHABET.len() as u64;
+                        c as char
+                    })
+                    .collect::<String>()
+            })
+            .find(|id| self.locate_item(id).is_none())
+            .unwrap_or_default()
+    }
+
+    /// Copies a `[[id]]` link to the selected item, giving the item an id
+    /// first when it has none. Links can be pasted in the item editor with
+    /// Ctrl-y.
+    pub fn copy_item_link(&mut self) {
+        self.load_linked_files();
+        let Some(item) = self
+            .active_board()
+            .current_list()
+            .and_then(|list| list.current_item())
+        else {
+            return;
+        };
+        let id = match item.id() {
+            Some(id) => id.to_string(),
+            None => {
+                let id = self.new_item_id();
+                let mut properties = item.properties.clone();
+                properties.insert(ID_PROPERTY.to_string(), id.clone());
+                if let Some(cmd) = self
+                    .active_board()
+                    .change_current_item_properties(properties)
+                {
+                    self.apply_board_command(cmd);
+                }
+                id
+            }
+        };
+        self.copied_link = Some(format!("[[{}]]", id));
+    }
+
+    /// Refreshes the link targets shown under the items of every loaded
+    /// board, and handed to the boards opened later, after the tree changed.
+    pub fn refresh_link_targets(&mut self) {
+        let targets: config::LinkTargets = self
+            .tree_items(0)
+            .into_iter()
+            .filter_map(|item| {
+                Some((
+                    item.id()?.to_string(),
+                    (item.title().to_string(), item.done),
+                ))
+            })
+            .collect();
+        for board in self.tree_boards(0) {
+            self.boards[board].config.link_targets = targets.clone();
+        }
+        self.config.board_config.link_targets = targets;
+    }
+
+    /// Navigates to the item the selected one links to first.
+    pub fn follow_link(&mut self) {
+        self.load_linked_files();
+        let Some(target) = self
+            .active_board()
+            .current_list()
+            .and_then(|list| list.current_item())
+            .and_then(|item| item.links().first().and_then(|id| self.locate_item(id)))
+        else {
+            return;
+        };
+        self.jump_to_item(target);
+    }
+
+    /// Opens the board holding an item and selects it.
+    pub fn jump_to_item(&mut self, (board, list, item): (usize, usize, usize)) {
+        self.jump_to_board(board);
+        if self.active_board_index() != board {
+            return;
+        }
+        SelectionBookmark {
+            list: Some(list),
+            item: Some(item),
+        }
+        .select(&mut self.boards[board]);
+    }
+}
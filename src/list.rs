@@ -1,6 +1,6 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::BTreeMap};
 
-use crate::config;
+use crate::{config, dates, sort::SortKey, timer};
 use ratatui::{
     prelude::*,
     widgets::{ListItem, ListState},
@@ -11,11 +11,88 @@ pub fn wrapping_presets(o: textwrap::Options) -> textwrap::Options {
         .word_splitter(textwrap::WordSplitter::NoHyphenation)
 }
 
+const DUE_PREFIX: &str = "due:";
+const RECURRENCE_PREFIX: &str = "every:";
+const PROPERTY_SEPARATOR: &str = "::";
+const MENTION_PREFIX: char = '@';
+pub const ID_PROPERTY: &str = "id";
+// Item property naming the markdown file that holds the item sub-board
+pub const FILE_PROPERTY: &str = "file";
+const BLOCKED_FILTER: &str = "is:blocked";
+// takes two cells, so the text of blocked items wraps one cell earlier
+const BLOCKED_FRINGE: &str = "🔒";
+const PRIORITY_PREFIX: &str = "!";
+pub const LOWEST_PRIORITY: u8 = 3;
+
+/// Returns the target id of a `[[id]]` link word.
+fn parse_link(word: &str) -> Option<&str> {
+    let id = word.strip_prefix("[[")?.strip_suffix("]]")?;
+    (!id.is_empty() && !id.contains(['[', ']'])).then_some(id)
+}
+
+// The name a tag or mention starts with, leaving out trailing punctuation,
+// e.g. `bug` in `bug,` or `Bob.smith` in `Bob.smith.`
+fn leading_name(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(text.len());
+    text[..end].trim_end_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Returns the name of an `@mention` word, leaving out trailing punctuation.
+fn parse_mention(word: &str) -> Option<&str> {
+    let name = leading_name(word.strip_prefix(MENTION_PREFIX)?);
+    (!name.is_empty()).then_some(name)
+}
+
+fn parse_priority(word: &str) -> Option<u8> {
+    word.strip_prefix(PRIORITY_PREFIX)?
+        .parse()
+        .ok()
+        .filter(|priority| (1..=LOWEST_PRIORITY).contains(priority))
+}
+
+/// Parses a `key:: value` property line.
+pub fn parse_property(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once(PROPERTY_SEPARATOR)?;
+    let key = key.trim();
+    if key.is_empty()
+        || key.contains(char::is_whitespace)
+        || !(value.is_empty() || value.starts_with(char::is_whitespace))
+    {
+        return None;
+    }
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+pub fn format_property(key: &str, value: &str) -> String {
+    format!("{}{} {}", key, PROPERTY_SEPARATOR, value)
+}
+
+/// Colour configured for a tag in the `[Tags]` section, or the generic one.
+pub fn tag_style<'a>(tag: &str, config: &'a config::BoardConfig) -> &'a config::Style {
+    config
+        .tag_styles
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(tag))
+        .map(|(_, style)| style)
+        .unwrap_or(&config.styles.tag)
+}
+
+fn priority_style(priority: u8, styles: &config::Styles) -> &config::Style {
+    match priority {
+        1 => &styles.priority_high,
+        2 => &styles.priority_medium,
+        _ => &styles.priority_low,
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct BoardItem {
     pub text: String,
     pub done: bool,
     pub board: Option<usize>,
+    pub properties: BTreeMap<String, String>,
 }
 
 impl BoardItem {
@@ -24,24 +101,352 @@ impl BoardItem {
             text: text.to_owned(),
             done: false,
             board: None,
+            properties: BTreeMap::new(),
         }
     }
     pub fn toggle(&mut self) {
         self.done = !self.done;
     }
+
+    /// Tags of the text, without the punctuation following them.
+    pub fn tags(&self) -> Vec<&str> {
+        self.text
+            .split_whitespace()
+            .flat_map(|word| word.split('#').skip(1))
+            .map(leading_name)
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    /// Text with the `old` tag renamed to `new`, or None when the item does
+    /// not have it. When the item already has the `new` tag, standalone
+    /// `#old` words are dropped instead, merging the two.
+    pub fn renamed_tag(&self, old: &str, new: &str) -> Option<String> {
+        let tags = self.tags();
+        if !tags.contains(&old) {
+            return None;
+        }
+        let merging = tags.contains(&new);
+        let mut text = String::new();
+        for chunk in self.text.split_inclusive(char::is_whitespace) {
+            let word = chunk.trim_end();
+            let separator = &chunk[word.len()..];
+            if merging && word.strip_prefix('#') == Some(old) {
+                // a dropped word keeps its line break, without a space
+                // left dangling before it
+                if !matches!(separator, " " | "\t") {
+                    text.truncate(text.trim_end_matches([' ', '\t']).len());
+                    text.push_str(separator);
+                }
+                continue;
+            }
+            let mut parts = word.split('#');
+            text.push_str(parts.next().unwrap_or_default());
+            for part in parts {
+                text.push('#');
+                match part.strip_prefix(old) {
+                    Some(rest) if leading_name(part) == old => {
+                        text.push_str(new);
+                        text.push_str(rest);
+                    }
+                    _ => text.push_str(part),
+                }
+            }
+            text.push_str(separator);
+        }
+        Some(text)
+    }
+
+    /// The first line of the text, naming the item in pickers, paths and logs.
+    pub fn title(&self) -> &str {
+        self.text.lines().next().unwrap_or_default()
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.properties.get(ID_PROPERTY).map(String::as_str)
+    }
+
+    /// A copy for the clipboard, without the sub-board and without the
+    /// properties that must stay unique: its id, linked file and running
+    /// timer.
+    pub fn copied(&self) -> BoardItem {
+        let mut copy = BoardItem {
+            board: None,
+            ..self.clone()
+        };
+        for key in [ID_PROPERTY, FILE_PROPERTY, timer::STARTED_PROPERTY] {
+            copy.properties.remove(key);
+        }
+        copy
+    }
+
+    /// The file holding the sub-board, relative to the main board file.
+    pub fn linked_file(&self) -> Option<&str> {
+        self.properties.get(FILE_PROPERTY).map(String::as_str)
+    }
+
+    /// Ids of the items this one links to with `[[id]]`.
+    pub fn links(&self) -> Vec<&str> {
+        self.text
+            .split_whitespace()
+            .filter_map(parse_link)
+            .collect()
+    }
+
+    /// Open items this one links to, which block it until they are done.
+    pub fn blockers<'a>(&self, targets: &'a config::LinkTargets) -> Vec<&'a str> {
+        self.links()
+            .into_iter()
+            .filter_map(|id| match targets.get(id) {
+                Some((text, false)) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn is_blocked(&self, targets: &config::LinkTargets) -> bool {
+        !self.done && !self.blockers(targets).is_empty()
+    }
+
+    // Width left for the text next to the fringe
+    fn text_width(&self, column_width: usize, config: &config::BoardConfig) -> usize {
+        let fringe = if self.is_blocked(&config.link_targets) {
+            2
+        } else {
+            1
+        };
+        column_width.max(fringe + 1) - fringe
+    }
+
+    pub fn mentions(&self) -> Vec<&str> {
+        self.text
+            .split_whitespace()
+            .filter_map(parse_mention)
+            .collect()
+    }
+
+    pub fn due_date(&self) -> Option<time::Date> {
+        self.text
+            .split_whitespace()
+            .find_map(|word| word.strip_prefix(DUE_PREFIX))
+            .and_then(|value| dates::parse(value, dates::today()))
+    }
+
+    pub fn recurrence(&self) -> Option<&str> {
+        self.text
+            .split_whitespace()
+            .find_map(|word| word.strip_prefix(RECURRENCE_PREFIX))
+    }
+
+    /// The open copy that replaces a recurring item once it is done, due on
+    /// the next occurrence after its current due date, or after today when
+    /// it has none. The copy starts afresh like a yanked copy, and without
+    /// the time tracked against the completed item.
+    pub fn next_occurrence(&self, today: time::Date) -> Option<BoardItem> {
+        let from = self.due_date().unwrap_or(today);
+        let due = dates::next_occurrence(self.recurrence()?, from)?;
+        let due = format!("{}{}", DUE_PREFIX, dates::format(due));
+        let mut replaced = false;
+        let mut text = self
+            .text
+            .split_inclusive(char::is_whitespace)
+            .map(|chunk| {
+                let word = chunk.trim_end();
+                if !replaced && word.starts_with(DUE_PREFIX) {
+                    replaced = true;
+                    format!("{}{}", due, &chunk[word.len()..])
+                } else {
+                    chunk.to_string()
+                }
+            })
+            .collect::<String>();
+        if !replaced {
+            let end = text.find('\n').unwrap_or(text.len());
+            text.insert_str(end, &format!(" {}", due));
+        }
+        let mut next = BoardItem {
+            text,
+            done: false,
+            ..self.copied()
+        };
+        next.properties.remove(timer::SPENT_PROPERTY);
+        Some(next)
+    }
+
+    pub fn priority(&self) -> Option<u8> {
+        self.text.split_whitespace().find_map(parse_priority)
+    }
+
+    /// Replaces any priority token in the text, putting the new one in front.
+    pub fn set_priority(&mut self, priority: Option<u8>) {
+        let text = self
+            .text
+            .split_inclusive(char::is_whitespace)
+            .filter(|chunk| parse_priority(chunk.trim_end()).is_none())
+            .collect::<String>();
+        let text = text.trim_end();
+        self.text = match priority {
+            Some(priority) if text.is_empty() => format!("{}{}", PRIORITY_PREFIX, priority),
+            Some(priority) => format!("{}{} {}", PRIORITY_PREFIX, priority, text),
+            None => text.to_string(),
+        };
+    }
+
+    /// Rewrites relative due dates such as `due:tomorrow` as ISO dates so
+    /// they keep pointing at the same day.
+    pub fn normalize_due_date(&mut self, today: time::Date) {
+        self.text = self
+            .text
+            .split_inclusive(char::is_whitespace)
+            .map(|chunk| {
+                let word = chunk.trim_end();
+                match word.strip_prefix(DUE_PREFIX) {
+                    Some(value) if dates::parse_iso(value).is_none() => {
+                        match dates::parse(value, today) {
+                            Some(date) => {
+                                format!(
+                                    "{}{}{}",
+                                    DUE_PREFIX,
+                                    dates::format(date),
+                                    &chunk[word.len()..]
+                                )
+                            }
+                            None => chunk.to_string(),
+                        }
+                    }
+                    _ => chunk.to_string(),
+                }
+            })
+            .collect();
+    }
+
+    /// Matches the item against a search query. Query terms of the form
+    /// `due:<date` and `due:>date` filter on the due date, `!1` to `!3` on the
+    /// priority, `@name` on mentions starting with the name, `is:blocked` on
+    /// open blockers, and the rest of the query is looked up in the item text.
+    pub fn matches(&self, query: &str, config: &config::BoardConfig) -> bool {
+        let today = dates::today();
+        let mut text_terms = Vec::new();
+        for term in query.split_whitespace() {
+            if term.eq_ignore_ascii_case(BLOCKED_FILTER) {
+                if !self.is_blocked(&config.link_targets) {
+                    return false;
+                }
+                continue;
+            }
+            if let Some(priority) = parse_priority(term) {
+                if self.priority() != Some(priority) {
+                    return false;
+                }
+                continue;
+            }
+            if let Some(name) = term.strip_prefix(MENTION_PREFIX) {
+                let name = name.to_lowercase();
+                if !self
+                    .mentions()
+                    .iter()
+                    .any(|mention| mention.to_lowercase().starts_with(&name))
+                {
+                    return false;
+                }
+                continue;
+            }
+            let due_filter = term
+                .strip_prefix("due:<")
+                .map(|value| (value, std::cmp::Ordering::Less))
+                .or_else(|| {
+                    term.strip_prefix("due:>")
+                        .map(|value| (value, std::cmp::Ordering::Greater))
+                });
+            match due_filter {
+                Some((value, ordering)) => {
+                    if let Some(date) = dates::parse(value, today)
+                        && self.due_date().map(|due| due.cmp(&date)) != Some(ordering)
+                    {
+                        return false;
+                    }
+                }
+                None => text_terms.push(term),
+            }
+        }
+        self.text
+            .to_lowercase()
+            .contains(&text_terms.join(" ").to_lowercase())
+    }
+
+    fn due_style(&self, word: &str, styles: &config::Styles) -> Option<config::Style> {
+        let date = dates::parse(word.strip_prefix(DUE_PREFIX)?, dates::today())?;
+        if !self.done && date < dates::today() {
+            Some(styles.due_overdue.clone())
+        } else {
+            Some(styles.due_upcoming.clone())
+        }
+    }
+    /// Number of rows the item takes when rendered into a column, including
+    /// the blank separator line.
+    pub fn height(&self, column_width: usize, config: &config::BoardConfig) -> usize {
+        let (s, o) = textwrap::unfill(&self.text);
+        textwrap::wrap(
+            &s,
+            wrapping_presets(o.width(self.text_width(column_width, config))),
+        )
+        .len()
+            + self.chip_lines(column_width, &config.property_chips).len()
+            + self.links().len()
+            + 1
+    }
+
+    /// Properties shown as `key:value` chips under the item text, packed
+    /// into as few lines as the column allows. An empty `shown` list shows
+    /// all of them but the item id.
+    fn chip_lines(&self, column_width: usize, shown: &[String]) -> Vec<Vec<String>> {
+        let mut lines: Vec<Vec<String>> = Vec::new();
+        let mut used = 0;
+        for (key, value) in self.properties.iter().filter(|(key, _)| {
+            if shown.is_empty() {
+                *key != ID_PROPERTY
+            } else {
+                shown.iter().any(|s| s.eq_ignore_ascii_case(key))
+            }
+        }) {
+            let chip = format!("{}:{}", key, value);
+            let width = chip.chars().count() + 1;
+            match lines.last_mut() {
+                Some(line) if used + width <= column_width.saturating_sub(1) => {
+                    line.push(chip);
+                    used += width;
+                }
+                _ => {
+                    lines.push(vec![chip]);
+                    used = width;
+                }
+            }
+        }
+        lines
+    }
+
     pub fn styled_text(
         &'_ self,
-        index: usize,
         column_width: usize,
         is_dimmable: bool,
-        styles: &config::Styles,
+        config: &config::BoardConfig,
     ) -> Text<'_> {
+        let styles = &config.styles;
+        let blocked = self.is_blocked(&config.link_targets);
+        let item_style = if blocked {
+            &styles.blocked
+        } else {
+            &styles.item
+        };
         let mut text = Text::default();
         let (s, o) = textwrap::unfill(&self.text);
-        let wrapped_text = textwrap::wrap(&s, wrapping_presets(o.width(column_width - 1)));
-        for line_text in wrapped_text.iter() {
+        let width = self.text_width(column_width, config);
+        let wrapped_text = textwrap::wrap(&s, wrapping_presets(o.width(width)));
+        for (i, line_text) in wrapped_text.iter().enumerate() {
             let mut line = Line::default();
-            if self.board.is_some() {
+            if blocked && i == 0 {
+                line += BLOCKED_FRINGE.fg(styles.blocked.fg).bg(styles.blocked.bg);
+            } else if self.board.is_some() || self.linked_file().is_some() {
                 line += "▍".fg(styles.fringe_on.fg).bg(styles.fringe_on.bg);
             } else {
                 line += "▍".fg(styles.fringe_off.fg).bg(styles.fringe_off.bg);
@@ -50,31 +455,66 @@ impl BoardItem {
             let mut action = String::new();
             let mut in_hash = false;
 
-            for ch in line_string.chars() {
-                if ch == '#' {
-                    if !in_hash {
-                        line += action.clone().fg(styles.item.fg).bg(styles.item.bg);
+            for chunk in line_string.split_inclusive(char::is_whitespace) {
+                if let Some(priority) = parse_priority(chunk.trim_end()) {
+                    let style = priority_style(priority, styles);
+                    line += action.clone().fg(item_style.fg).bg(item_style.bg);
+                    action.clear();
+                    line += chunk.trim_end().to_string().fg(style.fg).bg(style.bg);
+                    action.push_str(&chunk[chunk.trim_end().len()..]);
+                    continue;
+                }
+                if parse_link(chunk.trim_end()).is_some() {
+                    line += action.clone().fg(item_style.fg).bg(item_style.bg);
+                    action.clear();
+                    line += chunk.to_string().fg(styles.link.fg).bg(styles.link.bg);
+                    continue;
+                }
+                if let Some(name) = parse_mention(chunk) {
+                    line += action.clone().fg(item_style.fg).bg(item_style.bg);
+                    action.clear();
+                    line += chunk[..name.len() + 1]
+                        .to_string()
+                        .fg(styles.mention.fg)
+                        .bg(styles.mention.bg);
+                    action.push_str(&chunk[name.len() + 1..]);
+                    continue;
+                }
+                if let Some(style) = self.due_style(chunk.trim_end(), styles) {
+                    line += action.clone().fg(item_style.fg).bg(item_style.bg);
+                    action.clear();
+                    line += chunk.to_string().fg(style.fg).bg(style.bg);
+                    continue;
+                }
+                for ch in chunk.chars() {
+                    if ch == '#' {
+                        let style = if in_hash {
+                            tag_style(&action, config)
+                        } else {
+                            item_style
+                        };
+                        line += action.clone().fg(style.fg).bg(style.bg);
                         action.clear();
                         in_hash = true;
-                    }
-                    line += "#".fg(styles.tag_hashsign.fg).bg(styles.tag_hashsign.bg);
-                } else if ch.is_whitespace() {
-                    action.push(ch);
-                    if in_hash {
-                        line += action.clone().fg(styles.tag.fg).bg(styles.tag.bg);
+                        line += "#".fg(styles.tag_hashsign.fg).bg(styles.tag_hashsign.bg);
+                    } else if ch.is_whitespace() && in_hash {
+                        let style = tag_style(&action, config);
+                        action.push(ch);
+                        line += action.clone().fg(style.fg).bg(style.bg);
                         action.clear();
                         in_hash = false;
+                    } else {
+                        action.push(ch);
                     }
-                } else {
-                    action.push(ch);
                 }
             }
             if !action.is_empty() {
-                if in_hash {
-                    line += action.clone().fg(styles.tag.fg).bg(styles.tag.bg);
+                let style = if in_hash {
+                    tag_style(&action, config)
                 } else {
-                    line += action.clone().fg(styles.item.fg).bg(styles.item.bg);
-                }
+                    item_style
+                };
+                line += action.clone().fg(style.fg).bg(style.bg);
             }
             text.push_line(if self.done {
                 line.dim().crossed_out()
@@ -82,23 +522,44 @@ impl BoardItem {
                 line
             });
         }
+        for chips in self.chip_lines(column_width, &config.property_chips) {
+            let mut line = Line::default();
+            line += "▍".fg(styles.fringe_off.fg).bg(styles.fringe_off.bg);
+            for (i, chip) in chips.into_iter().enumerate() {
+                if i > 0 {
+                    line += Span::raw(" ");
+                }
+                line += chip.fg(styles.property_chip.fg).bg(styles.property_chip.bg);
+            }
+            text.push_line(if self.done { line.dim() } else { line });
+        }
+        for id in self.links() {
+            let mut line = Line::default();
+            line += "▍".fg(styles.fringe_off.fg).bg(styles.fringe_off.bg);
+            let target = match config.link_targets.get(id) {
+                Some((text, done)) => {
+                    let mark = if *done { "✓" } else { "→" };
+                    let target = format!("{} {}", mark, text);
+                    let target = target.fg(styles.link.fg).bg(styles.link.bg);
+                    if *done { target.crossed_out() } else { target }
+                }
+                None => format!("? {} not found", id).dark_gray(),
+            };
+            line += target;
+            text.push_line(line);
+        }
         text.extend([""]);
 
-        if index > 0 && is_dimmable {
-            text.dim()
-        } else {
-            text
-        }
+        if is_dimmable { text.dim() } else { text }
     }
 
     pub fn render(
         &'_ self,
-        index: usize,
         column_width: usize,
         is_dimmable: bool,
-        styles: &config::Styles,
+        config: &config::BoardConfig,
     ) -> ListItem<'_> {
-        ListItem::new(self.styled_text(index, column_width, is_dimmable, styles))
+        ListItem::new(self.styled_text(column_width, is_dimmable, config))
     }
 }
 
@@ -109,32 +570,184 @@ pub struct BoardList {
     pub state: RefCell<ListState>,
     pub selected_item_index: Option<usize>,
     pub width: u16,
+    pub width_override: Option<u16>,
+    pub collapsed: bool,
+    pub focus: Option<usize>,
+    pub wip_limit: Option<usize>,
     pub _color: Color,
 }
 
 impl BoardList {
+    pub fn from_heading(heading: &str) -> Self {
+        let mut list = Self::from_heading_attributes(heading);
+        if let Some((name, limit)) = list
+            .name
+            .strip_suffix(')')
+            .and_then(|n| n.rsplit_once(" ("))
+            && let Ok(limit) = limit.parse()
+        {
+            list.name = name.to_string();
+            list.wip_limit = Some(limit);
+        }
+        list
+    }
+
+    fn from_heading_attributes(heading: &str) -> Self {
+        let list = BoardList {
+            name: heading.to_string(),
+            ..Default::default()
+        };
+        if let Some(start) = heading.rfind(" {")
+            && heading.ends_with('}')
+        {
+            let mut parsed = BoardList {
+                name: heading[..start].to_string(),
+                ..Default::default()
+            };
+            for attribute in heading[start + 2..heading.len() - 1].split_whitespace() {
+                match attribute.split_once('=') {
+                    Some(("width", value)) => match value.parse() {
+                        Ok(width) => parsed.width_override = Some(width),
+                        Err(_) => return list,
+                    },
+                    Some(("focus", value)) => match value.parse() {
+                        Ok(focus) => parsed.focus = Some(focus),
+                        Err(_) => return list,
+                    },
+                    None if attribute == "collapsed" => parsed.collapsed = true,
+                    _ => return list,
+                }
+            }
+            return parsed;
+        }
+        list
+    }
+
+    pub fn heading(&self) -> String {
+        let mut attributes = Vec::new();
+        if let Some(width) = self.width_override {
+            attributes.push(format!("width={}", width));
+        }
+        if let Some(focus) = self.focus {
+            attributes.push(format!("focus={}", focus));
+        }
+        if self.collapsed {
+            attributes.push("collapsed".to_string());
+        }
+        let name = match self.wip_limit {
+            Some(limit) => format!("{} ({})", self.name, limit),
+            None => self.name.clone(),
+        };
+        if attributes.is_empty() {
+            name
+        } else {
+            format!("{} {{{}}}", name, attributes.join(" "))
+        }
+    }
+
+    /// Upper-cased list name, followed by the item count when the list has a
+    /// WIP limit.
+    pub fn title(&self) -> String {
+        match self.wip_limit {
+            Some(limit) => format!(
+                "{} {}/{}",
+                self.name.to_uppercase(),
+                self.items.len(),
+                limit
+            ),
+            None => self.name.to_uppercase(),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.wip_limit
+            .is_some_and(|limit| self.items.len() >= limit)
+    }
+
+    pub fn is_over_wip_limit(&self) -> bool {
+        self.wip_limit.is_some_and(|limit| self.items.len() > limit)
+    }
+
+    /// Returns the item indices in sorted order. The sort is stable, so
+    /// items that compare equal keep their current order.
+    pub fn sort_order(
+        &self,
+        key: SortKey,
+        progress: impl Fn(&BoardItem) -> Option<f64>,
+    ) -> Vec<usize> {
+        let items = &self.items;
+        let mut order = (0..items.len()).collect::<Vec<_>>();
+        match key {
+            SortKey::DoneLast => order.sort_by_key(|&i| items[i].done),
+            SortKey::Alphabetical => order.sort_by_cached_key(|&i| items[i].text.to_lowercase()),
+            SortKey::DueDate => order.sort_by_cached_key(|&i| {
+                let due_date = items[i].due_date();
+                (due_date.is_none(), due_date)
+            }),
+            SortKey::Tag => order.sort_by_cached_key(|&i| {
+                let tag = items[i].tags().first().map(|t| t.to_lowercase());
+                (tag.is_none(), tag)
+            }),
+            SortKey::Priority => order.sort_by_cached_key(|&i| {
+                let priority = items[i].priority();
+                (priority.is_none(), priority)
+            }),
+            SortKey::Progress => {
+                order.sort_by(|&a, &b| match (progress(&items[a]), progress(&items[b])) {
+                    (Some(a), Some(b)) => b.total_cmp(&a),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                })
+            }
+        }
+        order
+    }
+
     pub fn remove_item(&mut self, index: usize) {
         self.items.remove(index);
         self.clear_selection();
     }
     pub fn select_previous(&mut self) {
-        if let Some(selected_item_index) = self.selected_item_index {
-            if selected_item_index > 0 {
-                self.selected_item_index = Some((selected_item_index - 1).max(0))
-            }
+        if let Some(selected_item_index) = self.selected_item_index
+            && selected_item_index > 0
+        {
+            self.selected_item_index = Some(selected_item_index - 1)
         }
         self.set_selection();
     }
 
     pub fn select_next(&mut self) {
+        if let Some(selected_item_index) = self.selected_item_index
+            && selected_item_index < self.items.len() - 1
+        {
+            self.selected_item_index = Some(selected_item_index + 1)
+        }
+        self.set_selection();
+    }
+
+    pub fn select_next_page(&mut self, page: usize) {
         if let Some(selected_item_index) = self.selected_item_index {
-            if selected_item_index < self.items.len() - 1 {
-                self.selected_item_index = Some(selected_item_index + 1)
-            }
+            self.set_selection_index(selected_item_index + page);
         }
         self.set_selection();
     }
 
+    pub fn select_previous_page(&mut self, page: usize) {
+        if let Some(selected_item_index) = self.selected_item_index {
+            self.set_selection_index(selected_item_index.saturating_sub(page));
+        }
+        self.set_selection();
+    }
+
+    pub fn select_first(&mut self) {
+        self.set_selection_index(0);
+        self.set_selection();
+    }
+
+    pub fn select_last(&mut self) {
+        self.set_selection_index(self.items.len().saturating_sub(1));
+        self.set_selection();
+    }
+
     pub fn set_selection_index(&mut self, index: usize) {
         self.selected_item_index = if self.items.is_empty() {
             None
@@ -161,27 +774,27 @@ impl BoardList {
 
     #[allow(dead_code)]
     pub fn get_selected_item_text(&self) -> Option<&str> {
-        if !self.items.is_empty() {
-            if let Some(selected_item_index) = self.selected_item_index {
-                return Some(&self.items[selected_item_index].text);
-            }
+        if !self.items.is_empty()
+            && let Some(selected_item_index) = self.selected_item_index
+        {
+            return Some(&self.items[selected_item_index].text);
         }
         None
     }
 
     pub(crate) fn current_item(&self) -> Option<&BoardItem> {
-        if !self.items.is_empty() {
-            if let Some(selected_item_index) = self.selected_item_index {
-                return Some(&self.items[selected_item_index]);
-            }
+        if !self.items.is_empty()
+            && let Some(selected_item_index) = self.selected_item_index
+        {
+            return Some(&self.items[selected_item_index]);
         }
         None
     }
     pub(crate) fn current_item_mut(&mut self) -> Option<&mut BoardItem> {
-        if !self.items.is_empty() {
-            if let Some(selected_item_index) = self.selected_item_index {
-                return Some(&mut self.items[selected_item_index]);
-            }
+        if !self.items.is_empty()
+            && let Some(selected_item_index) = self.selected_item_index
+        {
+            return Some(&mut self.items[selected_item_index]);
         }
         None
     }
@@ -200,7 +813,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: Some(0),
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         board_list.remove_item(0);
@@ -216,7 +829,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: Some(1),
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         board_list.select_previous();
@@ -231,7 +844,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: Some(0),
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         board_list.select_next();
@@ -246,7 +859,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: None,
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         board_list.set_selection();
@@ -261,7 +874,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: Some(1),
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         assert_eq!(board_list.get_selected_item_text(), Some("Item 2"));
@@ -275,7 +888,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: Some(1),
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -292,7 +905,7 @@ mod tests {
             state: RefCell::new(ListState::default()),
             selected_item_index: Some(1),
             width: 10,
-            _color: Color::default(),
+            ..Default::default()
         };
 
         if let Some(item) = board_list.current_item_mut() {
@@ -301,4 +914,352 @@ mod tests {
 
         assert_eq!(board_list.items[1].text, "Updated Item");
     }
+
+    #[test]
+    fn test_heading_round_trip() {
+        let board_list = BoardList::from_heading("In Progress {width=30}");
+        assert_eq!(board_list.name, "In Progress");
+        assert_eq!(board_list.width_override, Some(30));
+        assert_eq!(board_list.heading(), "In Progress {width=30}");
+
+        let board_list = BoardList::from_heading("Done {width=30 collapsed}");
+        assert_eq!(board_list.name, "Done");
+        assert!(board_list.collapsed);
+        assert_eq!(board_list.heading(), "Done {width=30 collapsed}");
+
+        let board_list = BoardList::from_heading("Next {focus=3}");
+        assert_eq!(board_list.focus, Some(3));
+        assert_eq!(board_list.heading(), "Next {focus=3}");
+
+        let board_list = BoardList::from_heading("In Progress (3) {width=30}");
+        assert_eq!(board_list.name, "In Progress");
+        assert_eq!(board_list.wip_limit, Some(3));
+        assert_eq!(board_list.heading(), "In Progress (3) {width=30}");
+
+        let board_list = BoardList::from_heading("Later (maybe)");
+        assert_eq!(board_list.name, "Later (maybe)");
+        assert_eq!(board_list.wip_limit, None);
+    }
+
+    #[test]
+    fn test_heading_with_unknown_attributes() {
+        let board_list = BoardList::from_heading("Ideas {maybe}");
+        assert_eq!(board_list.name, "Ideas {maybe}");
+        assert_eq!(board_list.width_override, None);
+        assert_eq!(board_list.heading(), "Ideas {maybe}");
+    }
+
+    #[test]
+    fn test_select_pages() {
+        let mut board_list = BoardList {
+            items: (1..=10)
+                .map(|i| BoardItem::new(&format!("Item {}", i)))
+                .collect(),
+            selected_item_index: Some(0),
+            ..Default::default()
+        };
+
+        board_list.select_next_page(4);
+        assert_eq!(board_list.selected_item_index, Some(4));
+        board_list.select_next_page(8);
+        assert_eq!(board_list.selected_item_index, Some(9));
+        board_list.select_previous_page(3);
+        assert_eq!(board_list.selected_item_index, Some(6));
+        board_list.select_previous_page(10);
+        assert_eq!(board_list.selected_item_index, Some(0));
+    }
+
+    #[test]
+    fn test_select_first_and_last() {
+        let mut board_list = BoardList {
+            items: vec![BoardItem::new("Item 1"), BoardItem::new("Item 2")],
+            ..Default::default()
+        };
+
+        board_list.select_last();
+        assert_eq!(board_list.selected_item_index, Some(1));
+        board_list.select_first();
+        assert_eq!(board_list.selected_item_index, Some(0));
+
+        let mut empty_list = BoardList::default();
+        empty_list.select_last();
+        assert_eq!(empty_list.selected_item_index, None);
+    }
+
+    #[test]
+    fn test_due_date() {
+        let item = BoardItem::new("Ship it due:2026-11-01 #release");
+        assert_eq!(item.due_date(), Some(time::macros::date!(2026 - 11 - 01)));
+        assert_eq!(BoardItem::new("No deadline").due_date(), None);
+    }
+
+    #[test]
+    fn test_normalize_due_date() {
+        let mut item = BoardItem::new("Call back due:tomorrow\nthen due:soon");
+        item.normalize_due_date(time::macros::date!(2026 - 10 - 19));
+        assert_eq!(item.text, "Call back due:2026-10-20\nthen due:soon");
+    }
+
+    #[test]
+    fn test_matches_due_filters() {
+        let item = BoardItem::new("Renew license due:2026-11-01");
+        assert!(item.matches("renew due:<2026-11-02", &Default::default()));
+        assert!(!item.matches("renew due:>2026-11-02", &Default::default()));
+        assert!(item.matches("due:>2026-10-31", &Default::default()));
+        assert!(!item.matches("due:<2026-11-01", &Default::default()));
+        assert!(item.matches("due:<2026-1", &Default::default()));
+        assert!(!BoardItem::new("Someday").matches("due:<2026-11-02", &Default::default()));
+    }
+
+    fn list_for_sorting() -> BoardList {
+        BoardList {
+            items: vec![
+                BoardItem {
+                    done: true,
+                    ..BoardItem::new("banana #fruit due:2026-11-02")
+                },
+                BoardItem::new("Apple #fruit due:2026-11-03"),
+                BoardItem {
+                    board: Some(1),
+                    ..BoardItem::new("carrot #veggie")
+                },
+                BoardItem {
+                    board: Some(2),
+                    ..BoardItem::new("dates due:2026-11-01")
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_order() {
+        let list = list_for_sorting();
+        let progress = |item: &BoardItem| item.board.map(|b| 1.0 / b as f64);
+        assert_eq!(
+            list.sort_order(SortKey::DoneLast, progress),
+            vec![1, 2, 3, 0]
+        );
+        assert_eq!(
+            list.sort_order(SortKey::Alphabetical, progress),
+            vec![1, 0, 2, 3]
+        );
+        assert_eq!(
+            list.sort_order(SortKey::DueDate, progress),
+            vec![3, 0, 1, 2]
+        );
+        assert_eq!(list.sort_order(SortKey::Tag, progress), vec![0, 1, 2, 3]);
+        assert_eq!(
+            list.sort_order(SortKey::Progress, progress),
+            vec![2, 3, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_tags() {
+        let item = BoardItem::new("Fix #search and #status-bar#ui issues #");
+        assert_eq!(item.tags(), vec!["search", "status-bar", "ui"]);
+        let item = BoardItem::new("Fix #bug, #ui. (#wip) #c-");
+        assert_eq!(item.tags(), vec!["bug", "ui", "wip", "c"]);
+    }
+
+    #[test]
+    fn test_priority() {
+        let mut item = BoardItem::new("Fix login !2 #auth");
+        assert_eq!(item.priority(), Some(2));
+        assert_eq!(BoardItem::new("Shout !4 !").priority(), None);
+        item.set_priority(Some(1));
+        assert_eq!(item.text, "!1 Fix login #auth");
+        item.set_priority(None);
+        assert_eq!(item.text, "Fix login #auth");
+        assert!(BoardItem::new("!3 Tidy up").matches("!3 tidy", &Default::default()));
+        assert!(!BoardItem::new("!3 Tidy up").matches("!1", &Default::default()));
+        assert!(!BoardItem::new("Tidy up").matches("!3", &Default::default()));
+    }
+
+    #[test]
+    fn test_sort_by_priority() {
+        let list = BoardList {
+            items: vec![
+                BoardItem::new("none"),
+                BoardItem::new("low !3"),
+                BoardItem::new("!1 high"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(list.sort_order(SortKey::Priority, |_| None), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_wip_limit() {
+        let mut list = BoardList::from_heading("Doing (2)");
+        assert_eq!(list.title(), "DOING 0/2");
+        list.items = vec![BoardItem::new("a"), BoardItem::new("b")];
+        assert!(list.is_full());
+        assert!(!list.is_over_wip_limit());
+        list.items.push(BoardItem::new("c"));
+        assert_eq!(list.title(), "DOING 3/2");
+        assert!(list.is_over_wip_limit());
+        assert!(!BoardList::from_heading("Backlog").is_full());
+    }
+
+    #[test]
+    fn test_parse_property() {
+        assert_eq!(
+            parse_property("owner:: Alice Smith "),
+            Some(("owner".to_string(), "Alice Smith".to_string()))
+        );
+        assert_eq!(parse_property("see also:: this"), None);
+        assert_eq!(parse_property(":: value"), None);
+        assert_eq!(parse_property("std::vector overflow"), None);
+        assert_eq!(
+            parse_property("blocked::"),
+            Some(("blocked".to_string(), String::new()))
+        );
+        assert_eq!(parse_property("no separator"), None);
+    }
+
+    #[test]
+    fn test_property_chips() {
+        let mut item = BoardItem::new("Fix login");
+        item.properties
+            .insert("owner".to_string(), "alice".to_string());
+        item.properties
+            .insert("estimate".to_string(), "3".to_string());
+        let mut config = config::BoardConfig::default();
+        assert_eq!(
+            item.chip_lines(30, &config.property_chips),
+            vec![vec!["estimate:3", "owner:alice"]]
+        );
+        assert_eq!(item.height(30, &config), 3);
+        assert_eq!(item.chip_lines(15, &config.property_chips).len(), 2);
+        config.property_chips = vec!["Owner".to_string()];
+        assert_eq!(
+            item.chip_lines(30, &config.property_chips),
+            vec![vec!["owner:alice"]]
+        );
+        config.property_chips = vec!["ticket".to_string()];
+        assert_eq!(item.height(30, &config), 2);
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let today = time::macros::date!(2026 - 10 - 19);
+        let mut item = BoardItem {
+            done: true,
+            board: Some(3),
+            ..BoardItem::new("Water plants every:week due:2026-10-16 #home\nback porch too")
+        };
+        for (key, value) in [
+            ("id", "k3x9q2"),
+            ("file", "plants.md"),
+            ("spent", "25m"),
+            ("started", "2026-10-16 09:00"),
+            ("where", "garden"),
+        ] {
+            item.properties.insert(key.to_string(), value.to_string());
+        }
+        let next = item.next_occurrence(today).unwrap();
+        assert_eq!(
+            next.text,
+            "Water plants every:week due:2026-10-23 #home\nback porch too"
+        );
+        assert!(!next.done);
+        assert_eq!(next.board, None);
+        assert_eq!(
+            next.properties.into_iter().collect::<Vec<_>>(),
+            vec![("where".to_string(), "garden".to_string())]
+        );
+
+        let item = BoardItem::new("Take out bins every:mon\nbefore 8am");
+        assert_eq!(
+            item.next_occurrence(today).unwrap().text,
+            "Take out bins every:mon due:2026-10-26\nbefore 8am"
+        );
+        assert!(
+            BoardItem::new("One-off chore")
+                .next_occurrence(today)
+                .is_none()
+        );
+        assert!(
+            BoardItem::new("Odd every:blue")
+                .next_occurrence(today)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_links() {
+        let mut item = BoardItem::new("Needs [[k3x9q2]] and [[]] or [[a]b]] [[7zz0aa]]");
+        assert_eq!(item.links(), vec!["k3x9q2", "7zz0aa"]);
+        item.properties
+            .insert(ID_PROPERTY.to_string(), "abc123".to_string());
+        assert_eq!(item.id(), Some("abc123"));
+        assert!(item.chip_lines(30, &[]).is_empty());
+        assert_eq!(item.chip_lines(30, &["id".to_string()]).len(), 1);
+        let config = config::BoardConfig::default();
+        assert_eq!(item.height(80, &config), 4);
+    }
+
+    #[test]
+    fn test_blocked_items() {
+        let mut config = config::BoardConfig::default();
+        config
+            .link_targets
+            .insert("aaa111".to_string(), ("Design".to_string(), false));
+        config
+            .link_targets
+            .insert("bbb222".to_string(), ("Review".to_string(), true));
+        let mut item = BoardItem::new("Build after [[aaa111]] [[bbb222]] [[gone00]]");
+        assert_eq!(item.blockers(&config.link_targets), vec!["Design"]);
+        assert!(item.is_blocked(&config.link_targets));
+        assert!(item.matches("is:blocked build", &config));
+        assert!(!BoardItem::new("Build").matches("is:blocked", &config));
+        assert_eq!(item.text_width(30, &config), 28);
+
+        config.link_targets.get_mut("aaa111").unwrap().1 = true;
+        assert!(!item.is_blocked(&config.link_targets));
+        assert_eq!(item.text_width(30, &config), 29);
+        config.link_targets.get_mut("aaa111").unwrap().1 = false;
+        item.done = true;
+        assert!(!item.is_blocked(&config.link_targets));
+    }
+
+    #[test]
+    fn test_mentions() {
+        let item = BoardItem::new("Ask @alice, @Bob.smith. and bob@example.com @");
+        assert_eq!(item.mentions(), vec!["alice", "Bob.smith"]);
+        assert!(item.matches("@ali", &Default::default()));
+        assert!(item.matches("@bob ask", &Default::default()));
+        assert!(item.matches("@", &Default::default()));
+        assert!(!item.matches("@carol", &Default::default()));
+        assert!(!BoardItem::new("Nobody").matches("@", &Default::default()));
+    }
+
+    #[test]
+    fn test_renamed_tag() {
+        let item = BoardItem::new("Fix #bug in #ui#bug today");
+        assert_eq!(
+            item.renamed_tag("bug", "defect").as_deref(),
+            Some("Fix #defect in #ui#defect today")
+        );
+        assert_eq!(item.renamed_tag("missing", "other"), None);
+        let item = BoardItem::new("Fix #bug #defect");
+        assert_eq!(
+            item.renamed_tag("bug", "defect").as_deref(),
+            Some("Fix #defect")
+        );
+        let item = BoardItem::new("Fix #bug, then #bugs");
+        assert_eq!(
+            item.renamed_tag("bug", "defect").as_deref(),
+            Some("Fix #defect, then #bugs")
+        );
+
+        // merging keeps the lines apart
+        let item = BoardItem::new("fix #old\nsecond #new\n#old third\nlast #old");
+        assert_eq!(
+            item.renamed_tag("old", "new").as_deref(),
+            Some("fix\nsecond #new\nthird\nlast")
+        );
+    }
 }
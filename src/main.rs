@@ -53,18 +53,37 @@ use ratatui_textarea::{Input, Key, TextArea};
 use search::{FilteredBoardView, SearchController};
 use std::{collections::VecDeque, path::PathBuf};
 
+mod activity;
+mod archive;
+mod assignees;
 mod board;
 mod commands;
+mod complete;
 mod config;
+mod dates;
+mod files;
 mod help;
+mod levels;
+mod links;
 mod list;
 mod markdown;
+mod move_to;
+mod navigate;
+mod outline;
+mod popup;
+mod properties;
+mod report;
 mod search;
+mod sort;
+mod tags;
+mod timer;
 
 use board::*;
 use commands::*;
+use complete::draw_tag_suggestions;
 use help::*;
 use list::*;
+use sort::*;
 
 use ratatui::{
     DefaultTerminal, Frame,
@@ -123,11 +142,29 @@ struct App {
     input_mode: InputMode,
     search: SearchController,
     view: FilteredBoardView,
-    clipboard: Option<String>,
+    clipboard: Option<BoardItem>,
+    // a link copied with `b`, pasted with Ctrl-y in the editors until the
+    // next yank or cut
+    copied_link: Option<String>,
     config: config::Config,
+    // written back when tag colours change, None when not saving
+    config_file: Option<PathBuf>,
     edit_textarea: Option<TextArea<'static>>,
+    property_editor: properties::PropertyEditor,
+    tag_manager: tags::TagManager,
+    // appended to by every committed command, None when not logging
+    activity_log: Option<PathBuf>,
+    activity_view: activity::ActivityView,
+    // computed when the flow report opens rather than on every redraw
+    report: Option<(time::Date, report::FlowReport)>,
+    // shown in the status line until the next key press
+    warning: Option<String>,
+    board_picker: navigate::BoardPicker,
+    outline: outline::OutlineView,
+    move_picker: move_to::MovePicker,
 }
 
+#[derive(Clone)]
 struct BoardReference {
     board: usize,
     source_board: usize,
@@ -135,16 +172,34 @@ struct BoardReference {
     source_item: Option<usize>,
 }
 
+enum UndoableCommand {
+    Board(Box<dyn Command>),
+    Tree(Box<dyn TreeCommand>),
+}
+
 struct BoardCommand {
     board_index: usize,
-    command: Box<dyn Command>,
+    board_path: VecDeque<BoardReference>,
+    command: UndoableCommand,
+    activity: Option<String>,
 }
 
 impl BoardCommand {
-    pub fn new(board_index: usize, command: Box<dyn Command>) -> Self {
+    pub fn new(board_path: VecDeque<BoardReference>, command: Box<dyn Command>) -> Self {
         Self {
-            board_index,
-            command,
+            board_index: board_path.front().map(|r| r.board).unwrap_or(0),
+            board_path,
+            command: UndoableCommand::Board(command),
+            activity: None,
+        }
+    }
+
+    pub fn tree(board_path: VecDeque<BoardReference>, command: Box<dyn TreeCommand>) -> Self {
+        Self {
+            board_index: board_path.front().map(|r| r.board).unwrap_or(0),
+            board_path,
+            command: UndoableCommand::Tree(command),
+            activity: None,
         }
     }
 }
@@ -157,6 +212,16 @@ pub enum InputMode {
     EditItem,
     Search,
     Help,
+    Sort,
+    Properties,
+    Assignees,
+    Tags,
+    TimeReport,
+    Activity,
+    Report,
+    Boards,
+    Outline,
+    Move,
 }
 
 impl App {
@@ -167,15 +232,21 @@ impl App {
             .into();
         let config = App::load_config();
         let mut app = Self {
-            boards: vec![Board::default().with_config(config.board_config.clone())],
+            boards: vec![Board::default().with_config(config.board_config())],
             board_path: VecDeque::new(),
             filename: filename.clone(),
+            activity_log: config
+                .board_config
+                .activity_log
+                .then(|| filename.with_extension("activity.log")),
             config,
+            config_file: confy::get_configuration_file_path("oppenheimer", Some("config")).ok(),
             ..Default::default()
         };
         if std::path::Path::new(&filename).exists() {
             app.load_md(&filename, 0);
         }
+        app.refresh_link_targets();
         app
     }
 
@@ -183,10 +254,26 @@ impl App {
         confy::load("oppenheimer", Some("config")).unwrap()
     }
 
+    // Only the [Tags] section is saved, so the rest of the configuration
+    // file keeps what the user wrote rather than the runtime state
+    fn save_config(&mut self) {
+        let Some(path) = self.config_file.as_ref() else {
+            return;
+        };
+        let result = confy::load_path::<config::Config>(path).and_then(|mut config| {
+            config.tags = self.config.tags.clone();
+            confy::store_path(path, config)
+        });
+        if let Err(e) = result {
+            self.warning = Some(format!("Unable to save config {}: {}", path.display(), e));
+        }
+    }
+
     fn run(&mut self, mut terminal: DefaultTerminal) -> std::io::Result<()> {
         loop {
             terminal.draw(|frame| self.draw(frame))?;
             if let Event::Key(key) = event::read()? {
+                self.warning = None;
                 match self.input_mode {
                     InputMode::Normal => {
                         if key.modifiers == KeyModifiers::SHIFT {
@@ -197,6 +284,20 @@ impl App {
                                 KeyCode::Right | KeyCode::Char('L') => {
                                     self.shuffle_list_back();
                                 }
+                                KeyCode::Char('J') => self.active_board_mut().move_half_page_down(),
+                                KeyCode::Char('K') => self.active_board_mut().move_half_page_up(),
+                                KeyCode::Char('G') => self.active_board_mut().move_to_bottom(),
+                                KeyCode::Char('C') => self.expand_all_lists(),
+                                KeyCode::Char('A') => self.archive_done_items(true),
+                                KeyCode::Char('I') => self.input_mode = InputMode::TimeReport,
+                                KeyCode::Char('R') => self.open_flow_report(),
+                                KeyCode::Char('F') => self.toggle_linked_file(),
+                                KeyCode::Char('B') => self.open_breadcrumbs(),
+                                KeyCode::Char('T') => self.open_board_tree(),
+                                KeyCode::Char('O') => self.open_outline(),
+                                KeyCode::Char('M') => self.open_move_picker(),
+                                KeyCode::Char('P') => self.promote_selected_item(),
+                                KeyCode::Char('D') => self.demote_selected_item(),
                                 _ => {}
                             }
                         } else if key.modifiers == KeyModifiers::CONTROL {
@@ -216,7 +317,9 @@ impl App {
                                 KeyCode::Char('o') => {
                                     self.insert_list_to_board();
                                 }
-                                KeyCode::Char('d') => self.delete_selected_list(),
+                                KeyCode::Char('x') => self.delete_selected_list(),
+                                KeyCode::Char('u') => self.active_board_mut().move_half_page_up(),
+                                KeyCode::Char('d') => self.active_board_mut().move_half_page_down(),
                                 _ => {}
                             }
                         } else {
@@ -226,11 +329,27 @@ impl App {
                                 KeyCode::Char('?') => self.help(),
                                 KeyCode::Char('u') => self.undo(),
                                 KeyCode::Char('r') => self.redo(),
+                                KeyCode::Char('a') => self.archive_done_items(false),
+                                KeyCode::Char('!') => self.cycle_selected_item_priority(),
+                                KeyCode::Char('m') => self.edit_properties(),
+                                KeyCode::Char('@') => self.search_assignee(),
+                                KeyCode::Char('w') => self.input_mode = InputMode::Assignees,
+                                KeyCode::Char('t') => self.open_tag_manager(),
+                                KeyCode::Char('i') => self.toggle_timer(),
+                                KeyCode::Char('v') => self.open_activity_log(),
+                                KeyCode::Char('b') => self.copy_item_link(),
+                                KeyCode::Char('n') => self.follow_link(),
                                 KeyCode::Char('o') => self.insert_item_to_current_list(),
                                 KeyCode::Char('d') => self.delete_selected_item(),
                                 KeyCode::Char('x') => self.cut_selected_item(),
                                 KeyCode::Char('y') => self.yank_selected_item(),
                                 KeyCode::Char('p') => self.paste_item(),
+                                KeyCode::Char('s') => self.input_mode = InputMode::Sort,
+                                KeyCode::Char('c') => self.toggle_current_list_collapsed(),
+                                KeyCode::Char('f') => self.toggle_focus_mode(),
+                                KeyCode::Char('>') => self.widen_current_list(),
+                                KeyCode::Char('<') => self.narrow_current_list(),
+                                KeyCode::Char('=') => self.reset_current_list_width(),
                                 KeyCode::Enter => self.edit_current_item(),
                                 KeyCode::Esc => self.pop_board(),
                                 KeyCode::Char(' ') => self.toggle_selected_item(),
@@ -247,6 +366,12 @@ impl App {
                                 KeyCode::Left | KeyCode::Char('h') => {
                                     self.active_board_mut().move_left()
                                 }
+                                KeyCode::PageDown => self.active_board_mut().move_page_down(),
+                                KeyCode::PageUp => self.active_board_mut().move_page_up(),
+                                KeyCode::Home | KeyCode::Char('g') => {
+                                    self.active_board_mut().move_to_top()
+                                }
+                                KeyCode::End => self.active_board_mut().move_to_bottom(),
                                 _ => {}
                             }
                         }
@@ -278,6 +403,9 @@ impl App {
                         }
                     }
                     InputMode::EditItem => {
+                        if key.code == KeyCode::Tab && self.complete_tag_in_editor() {
+                            continue;
+                        }
                         if let Some(ref mut textarea) = self.edit_textarea {
                             match key.code {
                                 KeyCode::Esc => {
@@ -316,30 +444,58 @@ impl App {
                             KeyCode::Down | KeyCode::Up | KeyCode::Left | KeyCode::Right => {
                                 view.navigate_actual_board(self.active_board_mut(), &key);
                             }
+                            KeyCode::Tab => {
+                                if let Some(filter) = self.complete_tag_in_search() {
+                                    self.apply_search_filter(filter);
+                                }
+                            }
                             KeyCode::Esc | KeyCode::Enter => {
-                                if self.active_board().filter != "" {
+                                if !self.active_board().filter.is_empty() {
                                     view.select_item_from_view(self.active_board_mut());
                                 }
                                 self.active_board_mut().filter = "".to_string();
                                 self.input_mode = InputMode::Normal;
                             }
                             _ => {
-                                self.active_board_mut().filter = self.search.input(key);
-                                let updated_view = self.search.reflect(self.active_board());
-                                updated_view.update_view_selection(self.active_board_mut());
-                                self.view = updated_view;
-                                ()
+                                let filter = self.search.input(key);
+                                self.apply_search_filter(filter);
                             }
                         }
                     }
-                    InputMode::Help => {
+                    InputMode::Help
+                    | InputMode::Assignees
+                    | InputMode::TimeReport
+                    | InputMode::Report => {
                         self.input_mode = InputMode::Normal;
                     }
+                    InputMode::Sort => {
+                        self.input_mode = InputMode::Normal;
+                        if let KeyCode::Char(c) = key.code
+                            && let Some(sort_key) = SortKey::from_char(c)
+                        {
+                            self.sort_current_list(sort_key);
+                        }
+                    }
+                    InputMode::Properties => self.handle_properties_key(key),
+                    InputMode::Tags => self.handle_tags_key(key),
+                    InputMode::Activity => self.handle_activity_key(key),
+                    InputMode::Boards => self.handle_boards_key(key),
+                    InputMode::Outline => self.handle_outline_key(key),
+                    InputMode::Move => self.handle_move_key(key),
                 }
             }
         }
     }
 
+    // Text pasted with Ctrl-y in the editors: a copied link, or else the
+    // yanked or cut item
+    fn yank_text(&self) -> String {
+        self.copied_link
+            .clone()
+            .or_else(|| self.clipboard.as_ref().map(|item| item.text.clone()))
+            .unwrap_or_default()
+    }
+
     fn make_context(&'_ mut self) -> Context<'_> {
         // TODO: context should actually become app state
         let d = self.clipboard.clone();
@@ -349,13 +505,25 @@ impl App {
         }
     }
 
+    /// Records an applied command for undo, logs it and saves the board.
+    fn push_undo(&mut self, mut board_command: BoardCommand) {
+        board_command.activity = match &board_command.command {
+            UndoableCommand::Board(command) => command.activity(self.active_board()),
+            UndoableCommand::Tree(command) => command.activity(),
+        };
+        if let Some(activity) = &board_command.activity {
+            self.log_activity(activity);
+        }
+        self.undo.push_front(board_command);
+        self.save_board();
+        self.redo.clear();
+        self.refresh_link_targets();
+    }
+
     fn commit_board_command(&mut self) {
         let mut c = self.staged.take().unwrap();
         if c.finalize(self.active_board_mut()) {
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), c.to_cmd()));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), c.to_cmd()));
         } else {
             let mut context = self.make_context();
             c.revert(&mut context);
@@ -383,10 +551,7 @@ impl App {
         if let Some(mut cmd) = self.active_board_mut().prioritize_selected_item() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
@@ -394,42 +559,44 @@ impl App {
         if let Some(mut cmd) = self.active_board_mut().deprioritize_selected_item() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
     fn move_to_prev_list(&mut self) {
-        if self.active_board().current_list.is_some() {
+        if let Some(list) = self.active_board().current_list
+            && list > 0
+            && !self.refuses_items(self.active_board_index(), list - 1)
+        {
             let index = self.active_board().get_current_selection_index();
             if let Some(mut cmd) = self.active_board_mut().move_to_prev_list(index) {
                 let mut context = self.make_context();
                 cmd.apply(&mut context);
-                self.undo
-                    .push_front(BoardCommand::new(self.active_board_index(), cmd));
-                self.save_board();
-                self.redo.clear();
+                self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
             }
         }
     }
 
     fn move_to_next_list(&mut self) {
-        if self.active_board().current_list.is_some() {
+        if let Some(list) = self.active_board().current_list
+            && list + 1 < self.active_board().lists.len()
+            && !self.refuses_items(self.active_board_index(), list + 1)
+        {
             let index = self.active_board().get_current_selection_index();
             if let Some(mut cmd) = self.active_board_mut().move_to_next_list(index) {
                 let mut context = self.make_context();
                 cmd.apply(&mut context);
-                self.undo
-                    .push_front(BoardCommand::new(self.active_board_index(), cmd));
-                self.save_board();
-                self.redo.clear();
+                self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
             }
         }
     }
 
     fn insert_item_to_current_list(&mut self) {
+        if let Some(list) = self.active_board().current_list
+            && self.refuses_items(self.active_board_index(), list)
+        {
+            return;
+        }
         if let Some(cmd) = self.active_board_mut().insert_item_to_current_list() {
             self.staged = Some(cmd);
             self.input_mode = InputMode::EditItem;
@@ -440,6 +607,7 @@ impl App {
                 let mut ta = TextArea::default();
                 ta.set_wrap_mode(ratatui_textarea::WrapMode::Word);
                 ta.set_cursor_line_style(Style::new().bg(Color::Rgb(0, 0, 0)));
+                ta.set_yank_text(self.yank_text());
                 ta
             });
         }
@@ -449,10 +617,7 @@ impl App {
         if let Some(mut cmd) = self.active_board_mut().delete_selected_item() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
@@ -461,10 +626,8 @@ impl App {
             let mut context = self.make_context();
             cmd.apply(&mut context);
             self.clipboard = context.clipboard;
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.copied_link = None;
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
@@ -473,23 +636,25 @@ impl App {
             let mut context = self.make_context();
             cmd.apply(&mut context);
             self.clipboard = context.clipboard;
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.copied_link = None;
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
     fn paste_item(&mut self) {
-        if self.clipboard.is_some()
-            && let Some(mut cmd) = self.active_board_mut().paste_item()
+        if self.clipboard.is_none()
+            || self
+                .active_board()
+                .current_list
+                .is_some_and(|list| self.refuses_items(self.active_board_index(), list))
         {
+            return;
+        }
+        if let Some(mut cmd) = self.active_board_mut().paste_item() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.clipboard = context.clipboard;
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
@@ -509,21 +674,33 @@ impl App {
         if let Some(mut cmd) = self.active_board_mut().delete_selected_list() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
     fn toggle_selected_item(&mut self) {
+        let board = self.active_board();
+        if let Some(list) = board.current_list
+            && let Some(item) = board.lists[list].selected_item_index
+            && let Some(target) = board.next_occurrence_list(list, item)
+            && self.refuses_items(self.active_board_index(), target)
+        {
+            return;
+        }
+        self.refresh_link_targets();
+        let board = self.active_board();
+        if let Some(item) = board.current_list().and_then(|list| list.current_item())
+            && item.is_blocked(&board.config.link_targets)
+        {
+            self.warning = Some(format!(
+                "Completed while still waiting on: {}",
+                item.blockers(&board.config.link_targets).join(", ")
+            ));
+        }
         if let Some(mut cmd) = self.active_board_mut().toggle_selected_item() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
@@ -531,10 +708,7 @@ impl App {
         if let Some(mut cmd) = self.active_board_mut().shuffle_list_forward() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
-            self.redo.clear();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
         }
     }
 
@@ -542,9 +716,157 @@ impl App {
         if let Some(mut cmd) = self.active_board_mut().shuffle_list_back() {
             let mut context = self.make_context();
             cmd.apply(&mut context);
-            self.undo
-                .push_front(BoardCommand::new(self.active_board_index(), cmd));
-            self.save_board();
+            self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
+        }
+    }
+
+    fn apply_board_command(&mut self, mut cmd: Box<dyn Command>) {
+        let mut context = self.make_context();
+        cmd.apply(&mut context);
+        self.push_undo(BoardCommand::new(self.board_path.clone(), cmd));
+    }
+
+    fn cycle_selected_item_priority(&mut self) {
+        if let Some(cmd) = self.active_board().cycle_current_item_priority() {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    fn apply_tree_command(&mut self, mut cmd: Box<dyn TreeCommand>) {
+        let mut context = TreeContext {
+            board: self.active_board_index(),
+            boards: &mut self.boards,
+        };
+        cmd.apply(&mut context);
+        if let Some(warning) = cmd.take_warning() {
+            self.warning = Some(warning);
+        }
+        self.push_undo(BoardCommand::tree(self.board_path.clone(), cmd));
+    }
+
+    fn toggle_focus_mode(&mut self) {
+        self.config.board_config.dim_tailing_items = !self.config.board_config.dim_tailing_items;
+        for board in self.boards.iter_mut() {
+            board.config.dim_tailing_items = self.config.board_config.dim_tailing_items;
+        }
+    }
+
+    fn sort_current_list(&mut self, key: SortKey) {
+        let progress = |item: &BoardItem| {
+            item.board.and_then(|board| {
+                let (done, total) = self.board_progress(board);
+                (total > 0).then(|| done as f64 / total as f64)
+            })
+        };
+        if let Some(cmd) = self.active_board().sort_current_list(key, progress) {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    /// Counts the done and total items of a board and all its sub-boards.
+    fn board_progress(&self, board: usize) -> (usize, usize) {
+        self.boards[board]
+            .lists
+            .iter()
+            .flat_map(|list| list.items.iter())
+            .fold((0, 0), |(done, total), item| {
+                let (sub_done, sub_total) = item
+                    .board
+                    .map(|b| self.board_progress(b))
+                    .unwrap_or_default();
+                (done + item.done as usize + sub_done, total + 1 + sub_total)
+            })
+    }
+
+    /// A board followed by all its sub-boards, parents before children, each
+    /// with the titles of the items owning it on the way down from `board`.
+    fn walk_tree(&self, board: usize) -> Vec<(usize, Vec<&str>)> {
+        let mut boards = vec![(board, Vec::new())];
+        for item in self.boards[board].lists.iter().flat_map(|l| l.items.iter()) {
+            if let Some(sub_board) = item.board {
+                boards.extend(self.walk_tree(sub_board).into_iter().map(|(b, mut trail)| {
+                    trail.insert(0, item.title());
+                    (b, trail)
+                }));
+            }
+        }
+        boards
+    }
+
+    /// A board followed by all its sub-boards.
+    fn tree_boards(&self, board: usize) -> Vec<usize> {
+        self.walk_tree(board).into_iter().map(|(b, _)| b).collect()
+    }
+
+    /// The board path opening a board of the tree, from the root down to it.
+    fn board_path_to(&self, board: usize) -> Option<VecDeque<BoardReference>> {
+        if board == 0 {
+            return Some(VecDeque::new());
+        }
+        for (source_board, b) in self
+            .tree_boards(0)
+            .into_iter()
+            .map(|b| (b, &self.boards[b]))
+        {
+            for (l, list) in b.lists.iter().enumerate() {
+                if let Some(i) = list.items.iter().position(|item| item.board == Some(board)) {
+                    let mut path = self.board_path_to(source_board)?;
+                    path.push_front(BoardReference {
+                        board,
+                        source_board,
+                        source_list: Some(l),
+                        source_item: Some(i),
+                    });
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    /// Items of a board and all its sub-boards, parents before children.
+    fn tree_items(&self, board: usize) -> Vec<&BoardItem> {
+        self.boards[board]
+            .lists
+            .iter()
+            .flat_map(|list| list.items.iter())
+            .flat_map(|item| {
+                let mut items = vec![item];
+                if let Some(sub_board) = item.board {
+                    items.extend(self.tree_items(sub_board));
+                }
+                items
+            })
+            .collect()
+    }
+
+    fn toggle_current_list_collapsed(&mut self) {
+        if let Some(cmd) = self.active_board_mut().toggle_current_list_collapsed() {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    fn expand_all_lists(&mut self) {
+        if let Some(cmd) = self.active_board_mut().expand_all_lists() {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    fn widen_current_list(&mut self) {
+        if let Some(cmd) = self.active_board_mut().widen_current_list() {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    fn narrow_current_list(&mut self) {
+        if let Some(cmd) = self.active_board_mut().narrow_current_list() {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    fn reset_current_list_width(&mut self) {
+        if let Some(cmd) = self.active_board_mut().reset_current_list_width() {
+            self.apply_board_command(cmd);
         }
     }
 
@@ -560,6 +882,7 @@ impl App {
             textarea.set_wrap_mode(ratatui_textarea::WrapMode::Word);
             let style = Style::default();
             textarea.set_cursor_line_style(style);
+            textarea.set_yank_text(self.yank_text());
             self.edit_textarea = Some(textarea);
         }
     }
@@ -580,56 +903,96 @@ impl App {
     }
 
     fn push_board(&mut self) {
-        if let Some(current_list) = self.active_board().current_list() {
-            if let Some(current_item) = current_list.current_item() {
-                if let Some(board) = current_item.board {
-                    self.boards[board].config = self.config.board_config.clone();
+        if let Some(current_list) = self.active_board().current_list()
+            && let Some(current_item) = current_list.current_item()
+        {
+            if let Some(board) = current_item.board {
+                self.boards[board].config = self.config.board_config();
+                self.push_board_for_active_item(board);
+            } else if current_item.linked_file().is_some() {
+                if let Some(board) = self.load_linked_board() {
                     self.push_board_for_active_item(board);
-                } else {
-                    self.boards
-                        .push(Board::default().with_config(self.config.board_config.clone()));
-                    let new_board_index = self.boards.len() - 1;
-                    self.active_board_mut()
-                        .current_list_mut()
-                        .unwrap()
-                        .current_item_mut()
-                        .unwrap()
-                        .board = Some(new_board_index);
-                    self.push_board_for_active_item(new_board_index);
                 }
+            } else {
+                self.boards
+                    .push(Board::default().with_config(self.config.board_config()));
+                let new_board_index = self.boards.len() - 1;
+                self.active_board_mut()
+                    .current_list_mut()
+                    .unwrap()
+                    .current_item_mut()
+                    .unwrap()
+                    .board = Some(new_board_index);
+                self.push_board_for_active_item(new_board_index);
             }
         }
     }
 
     fn pop_board(&mut self) {
-        if let Some(r) = self.board_path.pop_front() {
-            if self.boards[r.board].lists.is_empty() {
-                if let (Some(source_list), Some(source_item)) = (r.source_list, r.source_item) {
-                    self.boards[r.source_board].lists[source_list].items[source_item].board = None;
+        if let Some(r) = self.board_path.pop_front()
+            && self.boards[r.board].lists.is_empty()
+            && let (Some(source_list), Some(source_item)) = (r.source_list, r.source_item)
+        {
+            self.boards[r.source_board].lists[source_list].items[source_item].board = None;
+        }
+    }
+
+    fn restore_board_path(&mut self, board_path: &VecDeque<BoardReference>) {
+        if let Some(list) = self.active_board_mut().current_list_mut() {
+            list.clear_selection();
+        }
+        for r in board_path.iter() {
+            if let (Some(source_list), Some(source_item)) = (r.source_list, r.source_item)
+                && let Some(item) = self.boards[r.source_board]
+                    .lists
+                    .get_mut(source_list)
+                    .and_then(|l| l.items.get_mut(source_item))
+            {
+                item.board = Some(r.board);
+            }
+        }
+        self.board_path = board_path.clone();
+    }
+
+    fn run_board_command(&mut self, board_command: &mut BoardCommand, revert: bool) {
+        self.restore_board_path(&board_command.board_path);
+        match &mut board_command.command {
+            UndoableCommand::Board(command) => {
+                let mut context = Context {
+                    board: &mut self.boards[board_command.board_index],
+                    clipboard: self.clipboard.clone(),
+                };
+                if revert {
+                    command.revert(&mut context);
+                } else {
+                    command.apply(&mut context);
+                }
+                self.clipboard = context.clipboard;
+            }
+            UndoableCommand::Tree(command) => {
+                let mut context = TreeContext {
+                    boards: &mut self.boards,
+                    board: board_command.board_index,
+                };
+                if revert {
+                    command.revert(&mut context);
+                } else {
+                    command.apply(&mut context);
+                }
+                if let Some(warning) = command.take_warning() {
+                    self.warning = Some(warning);
                 }
             }
         }
+        self.refresh_link_targets();
     }
 
     fn undo(&mut self) {
         if let Some(mut board_command) = self.undo.pop_front() {
-            if let Some(list) = self.active_board_mut().current_list_mut() {
-                list.clear_selection();
-            }
-            if board_command.board_index != self.active_board_index() {
-                self.board_path.push_front(BoardReference {
-                    board: board_command.board_index,
-                    source_board: self.active_board_index(),
-                    source_list: None,
-                    source_item: None,
-                });
+            self.run_board_command(&mut board_command, true);
+            if let Some(activity) = &board_command.activity {
+                self.log_activity(&format!("undid: {}", activity));
             }
-            let mut context = Context {
-                board: &mut self.boards[board_command.board_index],
-                clipboard: self.clipboard.clone(),
-            };
-            board_command.command.revert(&mut context);
-            self.clipboard = context.clipboard;
             self.redo.push_front(board_command);
             self.save_board();
         }
@@ -637,23 +1000,10 @@ impl App {
 
     fn redo(&mut self) {
         if let Some(mut board_command) = self.redo.pop_front() {
-            if let Some(list) = self.active_board_mut().current_list_mut() {
-                list.clear_selection();
-            }
-            if board_command.board_index != self.active_board_index() {
-                self.board_path.push_front(BoardReference {
-                    board: board_command.board_index,
-                    source_board: self.active_board_index(),
-                    source_list: None,
-                    source_item: None,
-                });
+            self.run_board_command(&mut board_command, false);
+            if let Some(activity) = &board_command.activity {
+                self.log_activity(&format!("redid: {}", activity));
             }
-            let mut context = Context {
-                board: &mut self.boards[board_command.board_index],
-                clipboard: self.clipboard.clone(),
-            };
-            board_command.command.apply(&mut context);
-            self.clipboard = context.clipboard;
             self.undo.push_front(board_command);
             self.save_board();
         }
@@ -690,6 +1040,21 @@ impl App {
             ),
             Span::styled("] HELP", Style::default()),
         ])]);
+        if let Some(warning) = &self.warning {
+            frame.render_widget(
+                Paragraph::new(format!("⚠ {}", warning))
+                    .centered()
+                    .style(Style::default().fg(Color::LightRed)),
+                rect,
+            );
+        }
+        if let Some(status) = self.timer_status(dates::now()) {
+            frame.render_widget(
+                Paragraph::new(Line::from(status).right_aligned())
+                    .style(Style::default().fg(Color::LightGreen)),
+                rect,
+            );
+        }
 
         frame.render_widget(list, rect);
     }
@@ -702,7 +1067,11 @@ impl App {
         ]);
         let [top, center, bottom] = horizontal.areas(frame.area());
         self.draw_status_line(frame, bottom);
-        let _lists_screen_area = self.active_board_mut().draw(frame, center);
+        if self.input_mode == InputMode::Outline {
+            self.draw_outline(frame, center);
+        } else {
+            self.active_board_mut().draw(frame, center);
+        }
         match self.input_mode {
             InputMode::EditTitle => {
                 if let Some(ref textarea) = self.edit_textarea {
@@ -731,12 +1100,16 @@ impl App {
                         rect,
                     );
                     frame.render_widget(textarea, rect);
+                    let suggestions = self.tag_suggestions(textarea);
                     self.active_board_mut().render_items_below_edit(frame, rect);
+                    draw_tag_suggestions(frame, rect, &suggestions);
                 }
             }
             InputMode::Search => {
                 self.search.draw(frame);
-                ()
+                let cursor = self.search.textarea().cursor().1 as u16;
+                let line = Rect::new(cursor, frame.area().height - 1, 1, 1);
+                draw_tag_suggestions(frame, line, &self.tag_suggestions(self.search.textarea()));
             }
             _ => {}
         }
@@ -744,6 +1117,33 @@ impl App {
         if self.input_mode == InputMode::Help {
             self.draw_help_popup(frame);
         }
+        if self.input_mode == InputMode::Sort {
+            show_sort_popup(frame);
+        }
+        if self.input_mode == InputMode::Properties {
+            self.draw_properties_popup(frame);
+        }
+        if self.input_mode == InputMode::Assignees {
+            self.draw_assignees_popup(frame);
+        }
+        if self.input_mode == InputMode::Tags {
+            self.draw_tag_manager(frame);
+        }
+        if self.input_mode == InputMode::TimeReport {
+            self.draw_time_report_popup(frame);
+        }
+        if self.input_mode == InputMode::Activity {
+            self.draw_activity_popup(frame);
+        }
+        if self.input_mode == InputMode::Report {
+            self.draw_report_popup(frame);
+        }
+        if self.input_mode == InputMode::Boards {
+            self.draw_board_picker(frame);
+        }
+        if self.input_mode == InputMode::Move {
+            self.draw_move_popup(frame);
+        }
     }
 
     fn draw_help_popup(&self, frame: &mut Frame) {
@@ -752,6 +1152,7 @@ impl App {
 
     fn save_board(&self) {
         self.write_md(&self.filename);
+        self.write_linked_files();
     }
 
     fn search(&mut self) {
@@ -759,7 +1160,1222 @@ impl App {
         self.input_mode = InputMode::Search;
     }
 
+    fn apply_search_filter(&mut self, filter: String) {
+        self.active_board_mut().filter = filter;
+        let updated_view = self.search.reflect(self.active_board());
+        updated_view.update_view_selection(self.active_board_mut());
+        self.view = updated_view;
+    }
+
+    /// Starts a search for the items of the person mentioned in the selected
+    /// item, or for any mentioned person when there is none.
+    fn search_assignee(&mut self) {
+        let query = self
+            .active_board()
+            .current_list()
+            .and_then(|list| list.current_item())
+            .and_then(|item| item.mentions().first().map(|name| format!("@{}", name)))
+            .unwrap_or_else(|| "@".to_string());
+        self.search.start_with(&query);
+        self.input_mode = InputMode::Search;
+        self.apply_search_filter(query);
+    }
+
     fn help(&mut self) {
         self.input_mode = InputMode::Help;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use time::Duration;
+
+    // Files written by the tests go to a directory of their own under the
+    // system temp dir, so failed tests leave nothing in the working tree
+    fn test_path(file: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("oppenheimer-tests");
+        fs::create_dir_all(&dir).expect("Unable to create test directory");
+        dir.join(file)
+    }
+
+    fn app_with_sub_board(name: &str) -> App {
+        App {
+            filename: test_path(&format!("test_undo_{}.md", name)),
+            boards: vec![
+                Board {
+                    lists: vec![BoardList {
+                        name: "root".to_string(),
+                        items: vec![
+                            BoardItem::new("root item 1"),
+                            BoardItem {
+                                board: Some(1),
+                                ..BoardItem::new("root item 2")
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                Board {
+                    lists: vec![
+                        BoardList {
+                            name: "sub 1".to_string(),
+                            items: vec![
+                                BoardItem::new("sub 1 item 1"),
+                                BoardItem::new("sub 1 item 2"),
+                                BoardItem::new("sub 1 item 3"),
+                            ],
+                            ..Default::default()
+                        },
+                        BoardList {
+                            name: "sub 2".to_string(),
+                            items: vec![BoardItem::new("sub 2 item 1")],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn enter_sub_board(app: &mut App, list: usize, item: usize) {
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(1);
+        app.push_board();
+        app.active_board_mut().current_list = Some(list);
+        app.active_board_mut().lists[list].set_selection_index(item);
+        app.active_board_mut().lists[list].set_selection();
+    }
+
+    fn leave_sub_board(app: &mut App) {
+        app.pop_board();
+        assert_eq!(app.active_board_index(), 0);
+    }
+
+    fn assert_selection(app: &App, list: usize, item: Option<usize>) {
+        assert_eq!(app.board_path.len(), 1);
+        let r = app.board_path.front().unwrap();
+        assert_eq!(r.board, 1);
+        assert_eq!(r.source_board, 0);
+        assert_eq!(r.source_list, Some(0));
+        assert_eq!(r.source_item, Some(1));
+        assert_eq!(app.active_board().current_list, Some(list));
+        assert_eq!(app.active_board().lists[list].selected_item_index, item);
+        for (i, l) in app.active_board().lists.iter().enumerate() {
+            if i != list {
+                assert_eq!(l.state.borrow().selected(), None);
+            }
+        }
+    }
+
+    fn undo_and_redo_from_root(
+        app: &mut App,
+        after_undo: (usize, Option<usize>),
+        after_redo: (usize, Option<usize>),
+    ) {
+        leave_sub_board(app);
+        app.undo();
+        assert_selection(app, after_undo.0, after_undo.1);
+        leave_sub_board(app);
+        app.redo();
+        assert_selection(app, after_redo.0, after_redo.1);
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_undo_change_text_restores_path() {
+        let mut app = app_with_sub_board("change_text");
+        enter_sub_board(&mut app, 0, 1);
+        app.edit_current_item();
+        app.active_board_mut().lists[0].items[1].text = "changed".to_string();
+        app.commit_board_command();
+        undo_and_redo_from_root(&mut app, (0, Some(1)), (0, Some(1)));
+        assert_eq!(app.boards[1].lists[0].items[1].text, "changed");
+    }
+
+    #[test]
+    fn test_undo_add_item_restores_path() {
+        let mut app = app_with_sub_board("add_item");
+        enter_sub_board(&mut app, 0, 1);
+        app.insert_item_to_current_list();
+        app.active_board_mut().lists[0].items[2].text = "new".to_string();
+        app.commit_board_command();
+        undo_and_redo_from_root(&mut app, (0, Some(1)), (0, Some(2)));
+        assert_eq!(app.boards[1].lists[0].items[2].text, "new");
+    }
+
+    #[test]
+    fn test_undo_add_list_restores_path() {
+        let mut app = app_with_sub_board("add_list");
+        enter_sub_board(&mut app, 0, 1);
+        app.insert_list_to_board();
+        app.active_board_mut().lists[1].name = "new".to_string();
+        app.commit_board_command();
+        leave_sub_board(&mut app);
+        app.undo();
+        assert_eq!(app.active_board_index(), 1);
+        assert_eq!(app.boards[1].lists.len(), 2);
+        leave_sub_board(&mut app);
+        app.redo();
+        assert_selection(&app, 1, None);
+        assert_eq!(app.boards[1].lists[1].name, "new");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_undo_delete_list_restores_path() {
+        let mut app = app_with_sub_board("delete_list");
+        enter_sub_board(&mut app, 1, 0);
+        app.delete_selected_list();
+        undo_and_redo_from_root(&mut app, (1, Some(0)), (0, Some(0)));
+        assert_eq!(app.boards[1].lists.len(), 1);
+    }
+
+    #[test]
+    fn test_undo_delete_item_restores_path() {
+        let mut app = app_with_sub_board("delete_item");
+        enter_sub_board(&mut app, 0, 2);
+        app.delete_selected_item();
+        undo_and_redo_from_root(&mut app, (0, Some(2)), (0, Some(1)));
+        assert_eq!(app.boards[1].lists[0].items.len(), 2);
+    }
+
+    #[test]
+    fn test_undo_shuffle_list_restores_path() {
+        let mut app = app_with_sub_board("shuffle_list");
+        enter_sub_board(&mut app, 1, 0);
+        app.shuffle_list_forward();
+        undo_and_redo_from_root(&mut app, (1, Some(0)), (0, Some(0)));
+        assert_eq!(app.boards[1].lists[0].name, "sub 2");
+    }
+
+    #[test]
+    fn test_undo_shuffle_item_restores_path() {
+        let mut app = app_with_sub_board("shuffle_item");
+        enter_sub_board(&mut app, 0, 1);
+        app.deprioritize_selected_item();
+        undo_and_redo_from_root(&mut app, (0, Some(1)), (0, Some(2)));
+        assert_eq!(app.boards[1].lists[0].items[2].text, "sub 1 item 2");
+    }
+
+    #[test]
+    fn test_undo_move_item_restores_path() {
+        let mut app = app_with_sub_board("move_item");
+        enter_sub_board(&mut app, 0, 2);
+        app.move_to_next_list();
+        undo_and_redo_from_root(&mut app, (0, Some(2)), (1, Some(1)));
+        assert_eq!(app.boards[1].lists[1].items[1].text, "sub 1 item 3");
+    }
+
+    #[test]
+    fn test_undo_toggle_item_restores_path() {
+        let mut app = app_with_sub_board("toggle_item");
+        enter_sub_board(&mut app, 0, 1);
+        app.toggle_selected_item();
+        undo_and_redo_from_root(&mut app, (0, Some(1)), (0, Some(1)));
+        assert!(app.boards[1].lists[0].items[1].done);
+    }
+
+    #[test]
+    fn test_undo_yank_item_restores_path() {
+        let mut app = app_with_sub_board("yank_item");
+        enter_sub_board(&mut app, 1, 0);
+        app.yank_selected_item();
+        undo_and_redo_from_root(&mut app, (1, Some(0)), (1, Some(0)));
+        assert_eq!(app.yank_text(), "sub 2 item 1");
+    }
+
+    #[test]
+    fn test_undo_cut_item_restores_path() {
+        let mut app = app_with_sub_board("cut_item");
+        enter_sub_board(&mut app, 0, 1);
+        app.cut_selected_item();
+        undo_and_redo_from_root(&mut app, (0, Some(1)), (0, Some(0)));
+        assert_eq!(app.yank_text(), "sub 1 item 2");
+    }
+
+    #[test]
+    fn test_undo_paste_item_restores_path() {
+        let mut app = app_with_sub_board("paste_item");
+        app.clipboard = Some(BoardItem::new("pasted"));
+        enter_sub_board(&mut app, 1, 0);
+        app.paste_item();
+        undo_and_redo_from_root(&mut app, (1, Some(0)), (1, Some(1)));
+        assert_eq!(app.boards[1].lists[1].items[1].text, "pasted");
+    }
+
+    #[test]
+    fn test_undo_relinks_unlinked_sub_board() {
+        let mut app = app_with_sub_board("relink");
+        app.boards[1].lists.clear();
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(1);
+        app.push_board();
+        app.insert_list_to_board();
+        app.active_board_mut().lists[0].name = "new".to_string();
+        app.commit_board_command();
+        app.undo();
+        leave_sub_board(&mut app);
+        assert_eq!(app.boards[0].lists[0].items[1].board, None);
+        app.redo();
+        assert_eq!(app.active_board_index(), 1);
+        assert_eq!(app.boards[0].lists[0].items[1].board, Some(1));
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_sort_by_sub_board_progress() {
+        let mut app = app_with_sub_board("sort_progress");
+        app.boards[1].lists[0].items[0].done = true;
+        assert_eq!(app.board_progress(0), (1, 6));
+        assert_eq!(app.board_progress(1), (1, 4));
+
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.sort_current_list(SortKey::Progress);
+        assert_eq!(app.boards[0].lists[0].items[0].text, "root item 2");
+        assert_eq!(app.active_board().get_current_selection_index(), 1);
+        app.undo();
+        assert_eq!(app.boards[0].lists[0].items[0].text, "root item 1");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_archive_done_items_with_sub_boards() {
+        let mut app = app_with_sub_board("archive");
+        app.boards[0].lists[0].items[1].done = true;
+        app.boards[1].lists[0].items[1].done = true;
+        app.archive_done_items(false);
+        assert_eq!(app.boards[0].lists[0].items.len(), 1);
+        assert_eq!(app.boards[0].lists[0].items[0].text, "root item 1");
+        let archive = fs::read_to_string(app.archive_path()).unwrap();
+        let date = dates::format(dates::today());
+        assert!(archive.contains(&format!("## {} root\n- [x] root item 2\n", date)));
+        assert!(archive.contains("    - [x] sub 1 item 2"));
+
+        app.undo();
+        assert_eq!(app.boards[0].lists[0].items[1].text, "root item 2");
+        assert!(!app.archive_path().exists());
+        app.redo();
+        assert_eq!(app.boards[0].lists[0].items.len(), 1);
+        assert!(app.archive_path().exists());
+        fs::remove_file(app.archive_path()).expect("Unable to remove test archive file");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_archive_done_items_recursively() {
+        let mut app = app_with_sub_board("archive_tree");
+        app.boards[0].lists[0].items[0].done = true;
+        app.boards[1].lists[0].items[1].done = true;
+        app.archive_done_items(true);
+        assert_eq!(app.boards[0].lists[0].items[0].text, "root item 2");
+        assert_eq!(app.boards[1].lists[0].items.len(), 2);
+        let first = fs::read_to_string(app.archive_path()).unwrap();
+        let date = dates::format(dates::today());
+        assert!(first.contains(&format!("## {} root\n- [x] root item 1\n", date)));
+        assert!(first.contains(&format!("## {} root item 2 〉 sub 1\n", date)));
+
+        app.boards[1].lists[0].items[0].done = true;
+        app.archive_done_items(true);
+        let second = fs::read_to_string(app.archive_path()).unwrap();
+        assert_eq!(second.matches("root item 2 〉 sub 1").count(), 1);
+        assert!(second.contains("- [x] sub 1 item 2\n- [x] sub 1 item 1\n"));
+
+        app.undo();
+        assert_eq!(fs::read_to_string(app.archive_path()).unwrap(), first);
+        assert_eq!(app.boards[1].lists[0].items[0].text, "sub 1 item 1");
+        fs::remove_file(app.archive_path()).expect("Unable to remove test archive file");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_archive_write_failure_keeps_items() {
+        let mut app = app_with_sub_board("archive_failure");
+        app.filename = test_path("missing-directory").join("board.md");
+        app.boards[0].lists[0].items[0].done = true;
+        app.archive_done_items(false);
+        assert!(
+            app.warning
+                .as_ref()
+                .unwrap()
+                .starts_with("Unable to write archive")
+        );
+        assert_eq!(app.boards[0].lists[0].items.len(), 2);
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn test_archive_refuses_unreadable_archive() {
+        let mut app = app_with_sub_board("archive_notes");
+        let notes = "Some notes\n\n- that aren't a board\n";
+        fs::write(app.archive_path(), notes).expect("Unable to write test archive file");
+        app.boards[0].lists[0].items[0].done = true;
+        app.archive_done_items(false);
+        assert!(
+            app.warning
+                .as_ref()
+                .unwrap()
+                .starts_with("Unable to archive to")
+        );
+        assert_eq!(fs::read_to_string(app.archive_path()).unwrap(), notes);
+        assert_eq!(app.boards[0].lists[0].items.len(), 2);
+        assert!(app.undo.is_empty());
+        fs::remove_file(app.archive_path()).expect("Unable to remove test archive file");
+    }
+
+    #[test]
+    fn test_edit_properties_in_popup() {
+        use crossterm::event::KeyEvent;
+        let mut app = app_with_sub_board("properties");
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        let press = |app: &mut App, code: KeyCode| {
+            app.handle_properties_key(KeyEvent::new(code, KeyModifiers::NONE))
+        };
+        app.edit_properties();
+        assert!(app.input_mode == InputMode::Properties);
+        press(&mut app, KeyCode::Char('o'));
+        for c in "owner:: alice".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.boards[0].lists[0].items[0].properties["owner"], "alice");
+
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.boards[0].lists[0].items[0].properties["owner"], "alicx");
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.boards[0].lists[0].items[0].properties.is_empty());
+        press(&mut app, KeyCode::Esc);
+        assert!(app.input_mode == InputMode::Normal);
+
+        app.undo();
+        assert_eq!(app.boards[0].lists[0].items[0].properties["owner"], "alicx");
+        app.undo();
+        assert_eq!(app.boards[0].lists[0].items[0].properties["owner"], "alice");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_open_items_per_assignee() {
+        let mut app = app_with_sub_board("assignees");
+        app.boards[0].lists[0].items[0].text = "Plan @alice".to_string();
+        app.boards[1].lists[0].items[0].text = "Draft @Alice and @bob".to_string();
+        app.boards[1].lists[0].items[1].text = "Review @bob @bob".to_string();
+        app.boards[1].lists[0].items[2].text = "Ship @carol".to_string();
+        app.boards[1].lists[0].items[2].done = true;
+        assert_eq!(
+            app.open_items_per_assignee(),
+            vec![
+                ("alice".to_string(), 2),
+                ("bob".to_string(), 2),
+                ("(unassigned)".to_string(), 2),
+            ]
+        );
+
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.search_assignee();
+        assert!(app.input_mode == InputMode::Search);
+        assert_eq!(app.active_board().filter, "@alice");
+    }
+
+    #[test]
+    fn test_tag_suggestions() {
+        let mut app = app_with_sub_board("tag_suggestions");
+        app.boards[0].lists[0].items[0].text = "Plan #release".to_string();
+        app.boards[1].lists[0].items[0].text = "Draft #docs #release".to_string();
+        app.boards[1].lists[0].items[1].text = "Review #docs #release".to_string();
+        app.boards[1].lists[1].items[0].text = "Ship #deploy".to_string();
+
+        let mut textarea = TextArea::new(vec!["New #".to_string()]);
+        textarea.move_cursor(ratatui_textarea::CursorMove::End);
+        assert_eq!(
+            app.tag_suggestions(&textarea),
+            vec![
+                ("release".to_string(), 3),
+                ("docs".to_string(), 2),
+                ("deploy".to_string(), 1),
+            ]
+        );
+        textarea.insert_str("d");
+        assert_eq!(app.tag_suggestions(&textarea)[0].0, "docs");
+
+        app.edit_textarea = Some(textarea);
+        assert!(app.complete_tag_in_editor());
+        assert_eq!(app.edit_textarea.as_ref().unwrap().lines(), ["New #docs"]);
+        assert!(!app.complete_tag_in_editor());
+
+        app.search.start_with("#rel");
+        assert_eq!(app.complete_tag_in_search().as_deref(), Some("#release"));
+    }
+
+    #[test]
+    fn test_rename_and_merge_tags() {
+        let mut app = app_with_sub_board("tags");
+        app.boards[0].lists[0].items[0].text = "Plan #bug".to_string();
+        app.boards[1].lists[0].items[0].text = "Draft #bug #defect".to_string();
+        app.boards[1].lists[1].items[0].text = "Ship #deploy".to_string();
+
+        app.rename_tag("bug", "defect");
+        assert_eq!(app.boards[0].lists[0].items[0].text, "Plan #defect");
+        assert_eq!(app.boards[1].lists[0].items[0].text, "Draft #defect");
+        assert_eq!(app.ranked_tags()[0], ("defect".to_string(), 2));
+
+        app.undo();
+        assert_eq!(app.boards[0].lists[0].items[0].text, "Plan #bug");
+        assert_eq!(app.boards[1].lists[0].items[0].text, "Draft #bug #defect");
+        app.redo();
+        assert_eq!(app.boards[0].lists[0].items[0].text, "Plan #defect");
+
+        app.set_tag_colour("defect", Some(Color::LightGreen));
+        assert_eq!(
+            tag_style("DEFECT", &app.boards[1].config).fg,
+            Color::LightGreen
+        );
+        app.set_tag_colour("defect", None);
+        assert!(app.boards[1].config.tag_styles.is_empty());
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_save_config_only_changes_tags() {
+        let path = test_path("test_save_config.toml");
+        let mut on_disk = config::Config::default();
+        on_disk.board_config.enforce_wip_limits = true;
+        confy::store_path(&path, on_disk).expect("Unable to write test config");
+
+        let mut app = App {
+            config_file: Some(path.clone()),
+            ..Default::default()
+        };
+        // runtime state from the focus toggle stays out of the file
+        app.config.board_config.dim_tailing_items = true;
+        app.set_tag_colour("bug", Some(Color::LightRed));
+        app.save_config();
+        assert!(app.warning.is_none());
+
+        let saved: config::Config = confy::load_path(&path).expect("Unable to read test config");
+        assert!(saved.board_config.enforce_wip_limits);
+        assert!(!saved.board_config.dim_tailing_items);
+        assert_eq!(saved.tags["bug"].fg, Color::LightRed);
+        fs::remove_file(&path).expect("Unable to remove test config");
+    }
+
+    #[test]
+    fn test_time_tracking() {
+        use time::macros::datetime;
+        let mut app = app_with_sub_board("timer");
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.boards[1].lists[1].items[0]
+            .properties
+            .insert("spent".to_string(), "1h".to_string());
+
+        app.toggle_timer_at(datetime!(2026-10-19 09:00));
+        assert_eq!(app.running_timer(), Some((0, 0, 0)));
+        assert_eq!(
+            app.timer_status(datetime!(2026-10-19 09:25)).as_deref(),
+            Some("⏱ root item 1 25m")
+        );
+
+        // starting another timer stops the running one
+        app.boards[0].lists[0].set_selection_index(1);
+        app.toggle_timer_at(datetime!(2026-10-19 09:30));
+        assert_eq!(app.running_timer(), Some((0, 0, 1)));
+        let first = &app.boards[0].lists[0].items[0].properties;
+        assert_eq!(first.get("spent").map(String::as_str), Some("30m"));
+        assert!(!first.contains_key("started"));
+
+        // the seconds past the last whole minute are kept
+        app.toggle_timer_at(datetime!(2026-10-19 10:45:30));
+        assert_eq!(app.running_timer(), None);
+        let second = &app.boards[0].lists[0].items[1].properties;
+        assert_eq!(second.get("spent").map(String::as_str), Some("1h 15m 30s"));
+        let (lists, sub_boards) = app.time_report(datetime!(2026-10-19 11:00));
+        let seconds = Duration::seconds(30);
+        assert_eq!(
+            lists,
+            vec![("root".to_string(), Duration::minutes(165) + seconds)]
+        );
+        assert_eq!(
+            sub_boards,
+            vec![("root item 2".to_string(), Duration::minutes(135) + seconds)]
+        );
+
+        app.undo();
+        assert_eq!(app.running_timer(), Some((0, 0, 1)));
+        app.undo();
+        assert_eq!(app.running_timer(), Some((0, 0, 0)));
+        assert!(app.boards[0].lists[0].items[1].properties.is_empty());
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_activity_log() {
+        let mut app = app_with_sub_board("activity");
+        let log = test_path("test_undo_activity.activity.log");
+        let _ = fs::remove_file(&log);
+        app.activity_log = Some(log.clone());
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+
+        app.toggle_selected_item();
+        app.delete_selected_item();
+        app.undo();
+        app.cycle_selected_item_priority();
+        app.open_activity_log();
+        let activities = app
+            .activities("")
+            .into_iter()
+            .map(|(_, trail, activity)| (trail.clone(), activity.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            activities,
+            vec![
+                (
+                    "".to_string(),
+                    "undid: deleted \"root item 1\" from root".to_string()
+                ),
+                (
+                    "".to_string(),
+                    "deleted \"root item 1\" from root".to_string()
+                ),
+                (
+                    "".to_string(),
+                    "completed \"root item 1\" in root".to_string()
+                ),
+            ]
+        );
+        // the view keeps the entries read when it was opened
+        fs::remove_file(&log).expect("Unable to remove test activity log");
+        assert_eq!(app.activities("COMPLETED root").len(), 1);
+        assert!(app.activities("moved").is_empty());
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_activity_log_failure_warns() {
+        let mut app = app_with_sub_board("activity_failure");
+        // a directory cannot be opened for appending
+        app.activity_log = Some(test_path(""));
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+
+        app.toggle_selected_item();
+        assert!(app.boards[0].lists[0].items[0].done);
+        assert!(
+            app.warning
+                .as_deref()
+                .is_some_and(|w| w.starts_with("Unable to write activity log"))
+        );
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_copy_and_follow_item_links() {
+        let mut app = app_with_sub_board("links");
+        app.boards[1].current_list = Some(0);
+        app.boards[1].lists[0].set_selection_index(1);
+        app.board_path = app.board_path_to(1).unwrap();
+        app.clipboard = Some(BoardItem::new("cut item"));
+        app.copy_item_link();
+        let id = app.boards[1].lists[0].items[1].id().unwrap().to_string();
+        assert_eq!(app.yank_text(), format!("[[{}]]", id));
+        // the item clipboard used by paste is left alone
+        assert_eq!(app.clipboard.as_ref().unwrap().text, "cut item");
+        app.copy_item_link();
+        assert_eq!(app.undo.len(), 1);
+
+        app.board_path.clear();
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.boards[0].lists[0].items[0].text = format!("Blocked by [[{}]]", id);
+        // targets were refreshed for every board by the command adding the id
+        assert_eq!(
+            app.boards[0].config.link_targets.get(&id),
+            Some(&("sub 1 item 2".to_string(), false))
+        );
+
+        app.follow_link();
+        assert_eq!(app.active_board_index(), 1);
+        assert_eq!(app.board_path.len(), 1);
+        assert_eq!(app.board_path[0].source_item, Some(1));
+        assert_eq!(app.active_board().current_list, Some(0));
+        assert_eq!(app.active_board().lists[0].selected_item_index, Some(1));
+
+        app.undo();
+        assert_eq!(app.locate_item(&id), None);
+        assert_eq!(app.boards[0].config.link_targets.get(&id), None);
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_move_sub_board_to_linked_file() {
+        let mut app = app_with_sub_board("linked_file");
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(1);
+        app.toggle_linked_file();
+        let file = "test_undo_linked_file-root-item-2.md";
+        assert_eq!(app.boards[0].lists[0].items[1].linked_file(), Some(file));
+        let main = fs::read_to_string(&app.filename).unwrap();
+        assert!(!main.contains("sub 1"));
+        assert!(main.contains("  file:: test_undo_linked_file-root-item-2.md"));
+        let linked = fs::read_to_string(app.linked_file_path(file).unwrap()).unwrap();
+        assert!(linked.starts_with("# root item 2\n## sub 1\n- [ ] sub 1 item 1\n"));
+
+        let mut reloaded = App {
+            filename: app.filename.clone(),
+            boards: vec![Board::default()],
+            ..Default::default()
+        };
+        reloaded.load_md(&app.filename.clone(), 0);
+        assert_eq!(reloaded.boards.len(), 1);
+        assert_eq!(reloaded.boards[0].lists[0].items[1].board, None);
+        reloaded.boards[0].current_list = Some(0);
+        reloaded.boards[0].lists[0].set_selection_index(1);
+        reloaded.push_board();
+        assert_eq!(reloaded.active_board_index(), 1);
+        assert_eq!(reloaded.active_board().lists[1].name, "sub 2");
+
+        reloaded.pop_board();
+        reloaded.toggle_linked_file();
+        assert_eq!(reloaded.boards[0].lists[0].items[1].linked_file(), None);
+        let main = fs::read_to_string(&app.filename).unwrap();
+        assert!(main.contains("    - [ ] sub 2 item 1"));
+
+        app.boards[0].lists[0].set_selection_index(0);
+        app.toggle_linked_file();
+        assert!(app.warning.is_some());
+        fs::remove_file(app.linked_file_path(file).unwrap())
+            .expect("Unable to remove linked markdown file");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_tree_wide_changes_load_linked_files() {
+        let mut app = app_with_sub_board("load_linked");
+        app.boards[1].lists[0].items[0].text = "sub 1 item 1 #bug".to_string();
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(1);
+        app.toggle_linked_file();
+        let file = "test_undo_load_linked-root-item-2.md";
+
+        let mut reloaded = App {
+            filename: app.filename.clone(),
+            boards: vec![Board::default()],
+            ..Default::default()
+        };
+        reloaded.load_md(&app.filename.clone(), 0);
+        assert_eq!(reloaded.boards.len(), 1);
+        reloaded.rename_tag("bug", "defect");
+        assert_eq!(reloaded.board_tree().len(), 2);
+        let linked = fs::read_to_string(app.linked_file_path(file).unwrap()).unwrap();
+        assert!(linked.contains("- [ ] sub 1 item 1 #defect\n"));
+
+        fs::remove_file(app.linked_file_path(file).unwrap())
+            .expect("Unable to remove linked markdown file");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_linked_files_stay_in_board_directory() {
+        let mut app = app_with_sub_board("linked_escape");
+        let outside = test_path("..").join("test_undo_linked_escape.md");
+        let board = "# outside\n## list\n- [ ] outside item\n";
+        fs::write(&outside, board).expect("Unable to write test markdown file");
+        assert_eq!(app.linked_file_path("../test_undo_linked_escape.md"), None);
+        assert_eq!(app.linked_file_path(&outside.display().to_string()), None);
+        assert!(app.linked_file_path("boards/sub.md").is_some());
+
+        let item = &mut app.boards[0].lists[0].items[0];
+        item.properties.insert(
+            "file".to_string(),
+            "../test_undo_linked_escape.md".to_string(),
+        );
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.push_board();
+        assert_eq!(app.active_board_index(), 0);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("Linked file ../test_undo_linked_escape.md is outside the board directory")
+        );
+        app.load_linked_files();
+        assert_eq!(app.boards.len(), 2);
+        app.save_board();
+        assert_eq!(fs::read_to_string(&outside).unwrap(), board);
+        fs::remove_file(&outside).expect("Unable to remove test markdown file");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_linked_files_that_are_not_new_boards() {
+        let mut app = app_with_sub_board("linked_refused");
+        let notes = test_path("test_undo_linked_refused-notes.md");
+        fs::write(&notes, "Some notes\n").expect("Unable to write test markdown file");
+        let cycle = test_path("test_undo_linked_refused-cycle.md");
+        let linking = "# root item 2\n## list\n- [ ] back to the main file\n  \
+                       file:: test_undo_linked_refused.md\n- [ ] to itself\n  \
+                       file:: test_undo_linked_refused-cycle.md\n";
+        fs::write(&cycle, linking).expect("Unable to write test markdown file");
+        app.write_md(&app.filename.clone());
+        for (item, file) in [
+            (0, "test_undo_linked_refused-notes.md"),
+            (1, "test_undo_linked_refused-cycle.md"),
+        ] {
+            let item = &mut app.boards[0].lists[0].items[item];
+            item.board = None;
+            item.properties.insert("file".to_string(), file.to_string());
+        }
+
+        app.load_linked_files();
+        let cycle_board = app.boards[0].lists[0].items[1].board.unwrap();
+        assert_eq!(app.boards[0].lists[0].items[0].board, None);
+        assert!(
+            app.boards[cycle_board].lists[0]
+                .items
+                .iter()
+                .all(|item| item.board.is_none())
+        );
+        assert!(
+            app.warning
+                .as_ref()
+                .unwrap()
+                .ends_with("is already part of the board")
+        );
+
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.push_board();
+        assert_eq!(app.active_board_index(), 0);
+        assert!(app.warning.as_ref().unwrap().contains("not loaded"));
+
+        app.save_board();
+        assert_eq!(fs::read_to_string(&notes).unwrap(), "Some notes\n");
+        assert_eq!(fs::read_to_string(&cycle).unwrap(), linking);
+        fs::remove_file(&notes).expect("Unable to remove test markdown file");
+        fs::remove_file(&cycle).expect("Unable to remove test markdown file");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_jump_to_boards() {
+        use crossterm::event::KeyEvent;
+        let mut app = app_with_sub_board("jump");
+        app.boards.push(Board {
+            lists: vec![BoardList {
+                name: "deep".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        app.boards[1].lists[1].items[0].board = Some(2);
+        let press = |app: &mut App, code: KeyCode| {
+            app.handle_boards_key(KeyEvent::new(code, KeyModifiers::NONE))
+        };
+        let names = |entries: Vec<navigate::BoardEntry>| {
+            entries
+                .into_iter()
+                .map(|e| (e.depth, e.name, e.board))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(app.board_tree()),
+            vec![
+                (0, "test_undo_jump".to_string(), 0),
+                (1, "root item 2".to_string(), 1),
+                (2, "sub 2 item 1".to_string(), 2),
+            ]
+        );
+
+        app.open_board_tree();
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.input_mode == InputMode::Normal);
+        assert_eq!(app.active_board_index(), 2);
+        assert_eq!(app.board_trail(), vec!["root item 2", "sub 2 item 1"]);
+        assert_eq!(
+            names(app.breadcrumbs()),
+            vec![
+                (0, "test_undo_jump".to_string(), 0),
+                (1, "root item 2".to_string(), 1),
+                (2, "sub 2 item 1".to_string(), 2),
+            ]
+        );
+
+        app.open_breadcrumbs();
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.active_board_index(), 0);
+        assert!(app.board_path.is_empty());
+    }
+
+    #[test]
+    fn test_edit_in_outline() {
+        use crossterm::event::KeyEvent;
+        let mut app = app_with_sub_board("outline");
+        app.boards[1].lists[0].items[1].text = "sub 1 item 2\ndetails".to_string();
+        let press = |app: &mut App, code: KeyCode, modifiers: KeyModifiers| {
+            app.handle_outline_key(KeyEvent::new(code, modifiers))
+        };
+        let selected_row = |app: &App| app.selected_outline_row().unwrap();
+        app.open_outline();
+        assert!(app.input_mode == InputMode::Outline);
+        assert_eq!(app.outline_rows().len(), 3);
+
+        press(&mut app, KeyCode::Char('G'), KeyModifiers::SHIFT);
+        press(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(app.outline_rows().len(), 9);
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        }
+        let sub_item = outline::OutlineRow::Item {
+            board: 1,
+            list: 0,
+            item: 1,
+            depth: 3,
+        };
+        assert_eq!(selected_row(&app), sub_item);
+
+        press(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(app.boards[1].lists[0].items[1].done);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        for c in " edited".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            app.boards[1].lists[0].items[1].text,
+            "sub 1 item 2 edited\ndetails"
+        );
+        assert_eq!(selected_row(&app), sub_item);
+
+        press(&mut app, KeyCode::Char('l'), KeyModifiers::CONTROL);
+        assert_eq!(app.boards[1].lists[0].items.len(), 2);
+        let outline::OutlineRow::Item { list, item, .. } = selected_row(&app) else {
+            panic!("moved item is not selected");
+        };
+        assert_eq!(list, 1);
+        assert_eq!(
+            app.boards[1].lists[1].items[item].text,
+            "sub 1 item 2 edited\ndetails"
+        );
+
+        press(&mut app, KeyCode::Char('u'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('u'), KeyModifiers::NONE);
+        assert_eq!(
+            app.boards[1].lists[0].items[1].text,
+            "sub 1 item 2\ndetails"
+        );
+        assert_eq!(selected_row(&app), sub_item);
+
+        press(&mut app, KeyCode::Char('h'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('h'), KeyModifiers::NONE);
+        assert!(app.boards[1].lists[0].collapsed);
+        assert_eq!(app.outline_rows().len(), 6);
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.input_mode == InputMode::Normal);
+        assert_eq!(app.active_board_index(), 1);
+        assert_eq!(app.active_board().current_list, Some(0));
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_move_item_to_another_board() {
+        use crossterm::event::KeyEvent;
+        let mut app = app_with_sub_board("move_to");
+        app.boards.push(Board {
+            lists: vec![BoardList {
+                name: "child".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        app.boards[0].lists[0].items[0].board = Some(2);
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        let press = |app: &mut App, code: KeyCode| {
+            app.handle_move_key(KeyEvent::new(code, KeyModifiers::NONE))
+        };
+        let labels = |app: &App, filter: &str| {
+            app.move_targets_for_selection(filter)
+                .into_iter()
+                .map(|t| t.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            labels(&app, ""),
+            vec![
+                "test_undo_move_to 〉 root item 2 〉 sub 1",
+                "test_undo_move_to 〉 root item 2 〉 sub 2",
+            ]
+        );
+
+        app.open_move_picker();
+        for c in "sub2".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.input_mode == InputMode::Normal);
+        assert_eq!(app.boards[0].lists[0].items.len(), 1);
+        let moved = &app.boards[1].lists[1].items[1];
+        assert_eq!(moved.text, "root item 1");
+        assert_eq!(moved.board, Some(2));
+        assert_eq!(app.tree_boards(0), vec![0, 1, 2]);
+        assert_eq!(
+            app.undo.front().unwrap().activity.as_deref(),
+            Some(
+                "moved \"root item 1\" from test_undo_move_to 〉 root \
+                 to test_undo_move_to 〉 root item 2 〉 sub 2"
+            )
+        );
+
+        app.jump_to_board(2);
+        app.undo();
+        assert_eq!(app.active_board_index(), 0);
+        assert_eq!(app.boards[0].lists[0].items[0].text, "root item 1");
+        assert_eq!(app.boards[1].lists[1].items.len(), 1);
+        app.redo();
+        assert_eq!(app.boards[1].lists[1].items[1].text, "root item 1");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_report_counts_moves_from_the_picker() {
+        let mut app = app_with_sub_board("move_to_report");
+        let log = test_path("test_undo_move_to_report.activity.log");
+        let _ = fs::remove_file(&log);
+        app.activity_log = Some(log.clone());
+        enter_sub_board(&mut app, 0, 0);
+        app.move_selected_item_to(1, 1);
+        assert_eq!(
+            app.undo.front().unwrap().activity.as_deref(),
+            Some("moved \"sub 1 item 1\" from sub 1 to sub 2")
+        );
+
+        app.open_flow_report();
+        assert!(app.input_mode == InputMode::Report);
+        let (today, report) = app.report.as_ref().unwrap();
+        assert_eq!(*today, dates::today());
+        let (_, counts) = report.flow.iter().find(|(l, _)| l == "sub 2").unwrap();
+        assert_eq!(counts.last(), Some(&1));
+        let (_, counts) = report
+            .throughput
+            .iter()
+            .find(|(r, _)| r == "sub 2")
+            .unwrap();
+        assert_eq!(counts.last(), Some(&1));
+        fs::remove_file(&log).expect("Unable to remove test activity log");
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_moves_across_boards_respect_wip_limits() {
+        let mut app = app_with_sub_board("move_wip");
+        app.config.board_config.enforce_wip_limits = true;
+        app.boards[0].lists[0]
+            .items
+            .push(BoardItem::new("root item 3"));
+        app.boards[0].lists[0].wip_limit = Some(3);
+        app.boards[1].lists[0].wip_limit = Some(3);
+        app.boards[1].lists[1].wip_limit = Some(1);
+
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.move_selected_item_to(1, 1);
+        assert_eq!(app.boards[1].lists[1].items.len(), 1);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("sub 2 has reached its WIP limit")
+        );
+
+        app.warning = None;
+        app.boards[0].lists[0].set_selection_index(2);
+        app.demote_selected_item();
+        assert_eq!(app.boards[1].lists[0].items.len(), 3);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("sub 1 has reached its WIP limit")
+        );
+
+        app.warning = None;
+        enter_sub_board(&mut app, 0, 0);
+        app.promote_selected_item();
+        assert_eq!(app.boards[0].lists[0].items.len(), 3);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("root has reached its WIP limit")
+        );
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn test_wip_limits_warn_on_moves_inserts_and_pastes() {
+        let mut app = app_with_sub_board("wip_warnings");
+        app.config.board_config.enforce_wip_limits = true;
+        app.boards[1].lists[1].wip_limit = Some(1);
+        enter_sub_board(&mut app, 0, 0);
+        let full = Some("sub 2 has reached its WIP limit");
+
+        app.move_to_next_list();
+        assert_eq!(app.boards[1].lists[1].items.len(), 1);
+        assert_eq!(app.warning.as_deref(), full);
+
+        app.warning = None;
+        app.yank_selected_item();
+        app.active_board_mut().current_list = Some(1);
+        app.active_board_mut().lists[1].set_selection_index(0);
+        app.paste_item();
+        assert_eq!(app.boards[1].lists[1].items.len(), 1);
+        assert_eq!(app.warning.as_deref(), full);
+
+        app.warning = None;
+        app.insert_item_to_current_list();
+        assert!(app.staged.is_none());
+        assert_eq!(app.warning.as_deref(), full);
+
+        app.warning = None;
+        app.boards[1].lists[0].wip_limit = Some(3);
+        app.move_to_prev_list();
+        assert_eq!(app.boards[1].lists[0].items.len(), 3);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("sub 1 has reached its WIP limit")
+        );
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_recurring_item_respects_wip_limit() {
+        let mut app = app_with_sub_board("recurring_wip");
+        app.config.board_config.enforce_wip_limits = true;
+        app.config.board_config.recurring_list = "sub 2".to_string();
+        app.boards[1].lists[1].wip_limit = Some(1);
+        app.boards[1].lists[0].items[0].text = "Chore every:week due:2026-10-16".to_string();
+        enter_sub_board(&mut app, 0, 0);
+
+        app.toggle_selected_item();
+        assert!(!app.boards[1].lists[0].items[0].done);
+        assert_eq!(app.boards[1].lists[1].items.len(), 1);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("sub 2 has reached its WIP limit")
+        );
+        assert!(app.undo.is_empty());
+
+        app.warning = None;
+        app.boards[1].lists[1].wip_limit = Some(2);
+        app.toggle_selected_item();
+        assert!(app.boards[1].lists[0].items[0].done);
+        assert_eq!(app.boards[1].lists[1].items.len(), 2);
+        assert_eq!(app.warning, None);
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_promote_item_to_parent_board() {
+        let mut app = app_with_sub_board("promote");
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.promote_selected_item();
+        assert!(app.warning.is_some());
+
+        enter_sub_board(&mut app, 0, 1);
+        app.promote_selected_item();
+        let texts = |app: &App, board: usize, list: usize| {
+            app.boards[board].lists[list]
+                .items
+                .iter()
+                .map(|item| item.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(&app, 0, 0),
+            vec!["root item 1", "root item 2", "sub 1 item 2"]
+        );
+        assert_eq!(texts(&app, 1, 0), vec!["sub 1 item 1", "sub 1 item 3"]);
+        assert_eq!(app.active_board().lists[0].selected_item_index, Some(0));
+
+        undo_and_redo_from_root(&mut app, (0, Some(1)), (0, Some(0)));
+        assert_eq!(texts(&app, 0, 0).len(), 3);
+    }
+
+    #[test]
+    fn test_demote_item_into_sub_board() {
+        let mut app = app_with_sub_board("demote");
+        app.boards[0].lists[0]
+            .items
+            .push(BoardItem::new("root item 3"));
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.demote_selected_item();
+        assert!(app.warning.is_some());
+
+        app.boards[0].lists[0].set_selection_index(2);
+        app.demote_selected_item();
+        assert_eq!(app.boards[0].lists[0].items.len(), 2);
+        assert_eq!(app.boards[1].lists[0].items[3].text, "root item 3");
+        assert_eq!(app.boards[1].lists.len(), 2);
+
+        app.boards[0].lists[0].set_selection_index(1);
+        app.demote_selected_item();
+        let items = &app.boards[0].lists[0].items;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].board, Some(2));
+        assert_eq!(app.boards[2].lists[0].name, "root");
+        assert_eq!(app.boards[2].lists[0].items[0].text, "root item 2");
+        assert_eq!(app.boards[2].lists[0].items[0].board, Some(1));
+        assert_eq!(app.tree_boards(0), vec![0, 2, 1]);
+        assert_eq!(app.active_board().lists[0].selected_item_index, Some(0));
+
+        app.undo();
+        let items = &app.boards[0].lists[0].items;
+        assert_eq!(items[0].board, None);
+        assert_eq!(items[1].text, "root item 2");
+        assert!(app.boards[2].lists.is_empty());
+        app.redo();
+        assert_eq!(app.boards[0].lists[0].items[0].board, Some(2));
+        assert_eq!(app.boards.len(), 3);
+        app.undo();
+        app.undo();
+        assert_eq!(app.boards[0].lists[0].items[2].text, "root item 3");
+        assert_eq!(app.boards[1].lists[0].items.len(), 3);
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+
+    #[test]
+    fn test_warn_when_completing_blocked_item() {
+        let mut app = app_with_sub_board("blocked");
+        app.boards[1].lists[0].items[0]
+            .properties
+            .insert("id".to_string(), "dep001".to_string());
+        app.boards[0].current_list = Some(0);
+        app.boards[0].lists[0].set_selection_index(0);
+        app.boards[0].lists[0].items[0].text = "Release after [[dep001]]".to_string();
+
+        app.toggle_selected_item();
+        assert!(app.boards[0].lists[0].items[0].done);
+        assert_eq!(
+            app.warning.as_deref(),
+            Some("Completed while still waiting on: sub 1 item 1")
+        );
+
+        app.warning = None;
+        app.toggle_selected_item();
+        app.boards[1].lists[0].items[0].done = true;
+        app.toggle_selected_item();
+        assert_eq!(app.warning, None);
+        fs::remove_file(&app.filename).expect("Unable to remove test markdown file");
+    }
+}
@@ -2,48 +2,76 @@ use std::fs::File;
 use std::io::Write;
 use std::{fs, path::Path};
 
-use crate::{App, Board, BoardItem, BoardList};
+use crate::{App, Board, BoardItem, BoardList, dates, list};
+
+// Item paragraphs hold the item text, followed by a trailing run of
+// `key:: value` lines
+fn loaded_item(text: &str, done: bool) -> BoardItem {
+    let lines: Vec<_> = text.lines().collect();
+    let text_end = lines
+        .iter()
+        .rposition(|line| list::parse_property(line).is_none())
+        .map_or(1, |last| last + 1);
+    let mut item = BoardItem {
+        text: lines[..text_end].join("\n"),
+        done,
+        board: None,
+        properties: lines[text_end..]
+            .iter()
+            .filter_map(|line| list::parse_property(line))
+            .collect(),
+    };
+    item.normalize_due_date(dates::today());
+    item
+}
+
+const LOADING_ERROR: &str = "file content is not an oppenheimer-compatible markdown";
 
 impl App {
-    pub fn load_sub_board(&mut self, li: &markdown::mdast::ListItem, board: usize) {
+    pub fn load_sub_board(
+        &mut self,
+        li: &markdown::mdast::ListItem,
+        board: usize,
+    ) -> Result<(), String> {
         for board_parent in li.children.iter() {
             if let markdown::mdast::Node::List(board_children) = board_parent {
                 for board_list in board_children.children.iter() {
                     let mut board_list_iterator = match board_list.children().iter().next() {
                         Some(child) => child.iter(),
-                        None => {
-                            self.quick_on_loading_error();
-                            return;
-                        }
+                        None => return Err(LOADING_ERROR.to_string()),
                     };
                     if let Some(list_name) = board_list_iterator.next() {
                         self.load_list_name(board, list_name);
                     } else {
-                        self.quick_on_loading_error();
+                        return Err(LOADING_ERROR.to_string());
                     }
 
                     if let Some(markdown::mdast::Node::List(item_list)) = board_list_iterator.next()
                     {
                         for list_item in item_list.children.iter() {
-                            self.load_list_item(board, list_item);
+                            self.load_list_item(board, list_item)?;
                         }
                     }
                 }
             }
         }
+        Ok(())
     }
     pub fn load_list_name(&mut self, board: usize, list_node: &markdown::mdast::Node) {
-        if let markdown::mdast::Node::Paragraph(paragraph_node) = list_node {
-            if let Some(markdown::mdast::Node::Text(text_node)) = paragraph_node.children.first() {
-                self.boards[board].lists.push(BoardList {
-                    name: text_node.value.clone(),
-                    ..Default::default()
-                });
-            }
+        if let markdown::mdast::Node::Paragraph(paragraph_node) = list_node
+            && let Some(markdown::mdast::Node::Text(text_node)) = paragraph_node.children.first()
+        {
+            self.boards[board]
+                .lists
+                .push(BoardList::from_heading(&text_node.value));
         }
     }
 
-    pub fn load_list_item(&mut self, board: usize, list_item: &markdown::mdast::Node) {
+    pub fn load_list_item(
+        &mut self,
+        board: usize,
+        list_item: &markdown::mdast::Node,
+    ) -> Result<(), String> {
         let is_checked = if let markdown::mdast::Node::ListItem(list_item_node) = list_item.clone()
         {
             list_item_node.checked.unwrap_or(false)
@@ -76,38 +104,53 @@ impl App {
                     .last()
                     .unwrap()
                     .items
-                    .push(BoardItem {
-                        text: text_node.value.clone(),
-                        done: is_checked,
-                        board: None,
-                    });
-                if has_sub_board {
-                    if let markdown::mdast::Node::ListItem(list_item_node) = list_item.clone() {
-                        self.boards.push(Board::default());
-
-                        self.boards[board]
-                            .lists
-                            .iter_mut()
-                            .last()
-                            .unwrap()
-                            .items
-                            .iter_mut()
-                            .last()
-                            .unwrap()
-                            .board = Some(self.boards.len() - 1);
-                        self.load_sub_board(&list_item_node, self.boards.len() - 1);
-                    }
+                    .push(loaded_item(&text_node.value, is_checked));
+                if has_sub_board
+                    && let markdown::mdast::Node::ListItem(list_item_node) = list_item.clone()
+                {
+                    self.boards.push(Board::default());
+
+                    self.boards[board]
+                        .lists
+                        .iter_mut()
+                        .last()
+                        .unwrap()
+                        .items
+                        .iter_mut()
+                        .last()
+                        .unwrap()
+                        .board = Some(self.boards.len() - 1);
+                    self.load_sub_board(&list_item_node, self.boards.len() - 1)?;
                 }
             }
         }
+        Ok(())
     }
 
+    /// Loads a board file, quitting with an error when it isn't one.
     pub fn load_md(&mut self, filename: &Path, board: usize) {
-        use std::fs;
-        let markdown_content =
-            fs::read_to_string(filename.to_str().unwrap()).expect("Unable to read file");
+        if let Err(e) = self.read_md(filename, board) {
+            self.quick_on_loading_error(&e);
+        }
+    }
+
+    /// Loads a board file into `board`, or leaves the boards as they were
+    /// when the file can't be read or holds anything but lists of items,
+    /// such as plain text.
+    pub fn read_md(&mut self, filename: &Path, board: usize) -> Result<(), String> {
+        let loaded = self.boards.len();
+        let result = fs::read_to_string(filename)
+            .map_err(|e| format!("Unable to read {}: {}", filename.display(), e))
+            .and_then(|content| self.parse_md(&content, board));
+        if result.is_err() {
+            self.boards.truncate(loaded);
+        }
+        result
+    }
+
+    fn parse_md(&mut self, markdown_content: &str, board: usize) -> Result<(), String> {
         let x = markdown::to_mdast(
-            &markdown_content,
+            markdown_content,
             &markdown::ParseOptions {
                 constructs: markdown::Constructs {
                     gfm_task_list_item: true,
@@ -116,9 +159,9 @@ impl App {
                 ..Default::default()
             },
         )
-        .unwrap();
+        .map_err(|_| LOADING_ERROR.to_string())?;
         let mut lists: Vec<BoardList> = Vec::new();
-        for e in x.children().iter().next().unwrap().iter() {
+        for e in x.children().into_iter().flatten() {
             match e {
                 markdown::mdast::Node::List(l) => {
                     for i in l.children.iter() {
@@ -132,21 +175,17 @@ impl App {
                                 for t in p.children.iter() {
                                     if let markdown::mdast::Node::Text(t) = t {
                                         if let Some(list) = lists.iter_mut().last() {
-                                            list.items.push(BoardItem {
-                                                text: t.value.clone(),
-                                                done: checked,
-                                                board: None,
-                                            });
-                                            if let markdown::mdast::Node::ListItem(li) = i.clone() {
-                                                if li.children.len() > 1 {
-                                                    self.boards.push(Board::default());
-                                                    list.items.iter_mut().last().unwrap().board =
-                                                        Some(self.boards.len() - 1);
-                                                    self.load_sub_board(&li, self.boards.len() - 1);
-                                                }
+                                            list.items.push(loaded_item(&t.value, checked));
+                                            if let markdown::mdast::Node::ListItem(li) = i.clone()
+                                                && li.children.len() > 1
+                                            {
+                                                self.boards.push(Board::default());
+                                                list.items.iter_mut().last().unwrap().board =
+                                                    Some(self.boards.len() - 1);
+                                                self.load_sub_board(&li, self.boards.len() - 1)?;
                                             }
                                         } else {
-                                            self.quick_on_loading_error();
+                                            return Err(LOADING_ERROR.to_string());
                                         }
                                     }
                                 }
@@ -154,28 +193,43 @@ impl App {
                         }
                     }
                 }
-                markdown::mdast::Node::Heading(h) => {
-                    if h.depth == 2 {
-                        if let Some(markdown::mdast::Node::Text(name)) = h.children.first() {
-                            lists.push(BoardList {
-                                name: name.value.clone(),
-                                ..Default::default()
-                            });
-                        } else {
-                            self.quick_on_loading_error();
-                        }
+                markdown::mdast::Node::Heading(h) if h.depth == 2 => {
+                    if let Some(markdown::mdast::Node::Text(name)) = h.children.first() {
+                        lists.push(BoardList::from_heading(&name.value));
+                    } else {
+                        return Err(LOADING_ERROR.to_string());
                     }
                 }
-                _ => {}
+                markdown::mdast::Node::Heading(h) if h.depth == 1 => {}
+                _ => return Err(LOADING_ERROR.to_string()),
             }
         }
         self.boards[board].lists = lists;
+        Ok(())
+    }
+
+    fn write_properties(&self, file: &mut impl Write, item: &BoardItem, indent: usize) {
+        for (key, value) in item.properties.iter() {
+            writeln!(
+                file,
+                "{}{}",
+                " ".repeat(indent),
+                list::format_property(key, &value.replace('\n', " "))
+            )
+            .expect("Unable to write to file");
+        }
     }
 
-    pub fn write_sub_board(&self, file: &mut File, board: usize, level: usize) {
+    pub fn write_sub_board(
+        &self,
+        file: &mut impl Write,
+        board: usize,
+        level: usize,
+        linked: &[usize],
+    ) {
         let board = &self.boards[board];
         for list in board.lists.iter() {
-            writeln!(file, "{}- {}", " ".repeat(level * 2), list.name)
+            writeln!(file, "{}- {}", " ".repeat(level * 2), list.heading())
                 .expect("Unable to write to file");
             for item in list.items.iter() {
                 let checkmark = if item.done { "[x]" } else { "[ ]" };
@@ -187,36 +241,61 @@ impl App {
                     item.text.replace('\n', " ")
                 )
                 .expect("Unable to write to file");
-                if let Some(board_index) = item.board {
-                    self.write_sub_board(file, board_index, level + 2);
+                self.write_properties(file, item, (level * 2) + 4);
+                if let Some(board_index) = item.board
+                    && !linked.contains(&board_index)
+                {
+                    self.write_sub_board(file, board_index, level + 2, linked);
                 }
             }
         }
     }
 
     pub fn write_md(&self, filename: &Path) {
-        let board = &self.boards[0];
+        self.write_board_md(filename, 0, "Project Name");
+    }
+
+    /// Writes a board and its inline sub-boards to a file of its own.
+    pub fn write_board_md(&self, filename: &Path, board: usize, title: &str) {
         let temp_file_path = format!("{}.tmp", filename.display());
         let mut file = File::create(&temp_file_path).expect("Unable to create temporary file");
-        writeln!(file, "# Project Name").expect("Unable to write to file");
+        self.write_board_to(&mut file, board, title);
+        fs::rename(temp_file_path, filename.to_str().unwrap())
+            .expect("Unable to rename temporary file");
+    }
+
+    pub fn write_md_to(&self, file: &mut impl Write) {
+        self.write_board_to(file, 0, "Project Name");
+    }
+
+    // Sub-boards are written inline, except those saved to linked files
+    fn write_board_to(&self, file: &mut impl Write, board: usize, title: &str) {
+        let linked: Vec<usize> = self
+            .linked_boards()
+            .into_iter()
+            .map(|(board, ..)| board)
+            .collect();
+        let board = &self.boards[board];
+        writeln!(file, "# {}", title).expect("Unable to write to file");
         for list in board.lists.iter() {
-            writeln!(file, "## {}", list.name).expect("Unable to write to file");
+            writeln!(file, "## {}", list.heading()).expect("Unable to write to file");
             for item in list.items.iter() {
                 let checkmark = if item.done { "[x]" } else { "[ ]" };
                 writeln!(file, "- {} {}", checkmark, item.text.replace('\n', " "))
                     .expect("Unable to write to file");
-                if let Some(board_index) = item.board {
-                    self.write_sub_board(&mut file, board_index, 1);
+                self.write_properties(file, item, 2);
+                if let Some(board_index) = item.board
+                    && !linked.contains(&board_index)
+                {
+                    self.write_sub_board(file, board_index, 1, &linked);
                 }
             }
         }
-        fs::rename(temp_file_path, filename.to_str().unwrap())
-            .expect("Unable to rename temporary file");
     }
 
-    fn quick_on_loading_error(&self) {
+    fn quick_on_loading_error(&self, error: &str) {
         ratatui::restore();
-        eprintln!("Error: file content is not an oppenheimer-compatible markdown");
+        eprintln!("Error: {}", error);
         std::process::exit(0);
     }
 }
@@ -290,11 +369,13 @@ mod tests {
                     text: "Task 1".to_string(),
                     done: false,
                     board: None,
+                    ..Default::default()
                 },
                 BoardItem {
                     text: "Task 2 with sub-items".to_string(),
                     done: true,
                     board: Some(1),
+                    ..Default::default()
                 },
             ],
             ..Default::default()
@@ -308,11 +389,13 @@ mod tests {
                         text: "Sub-task 1".to_string(),
                         done: true,
                         board: None,
+                        ..Default::default()
                     },
                     BoardItem {
                         text: "Sub-task 2".to_string(),
                         done: false,
                         board: None,
+                        ..Default::default()
                     },
                 ],
                 ..Default::default()
@@ -338,4 +421,67 @@ mod tests {
 
         fs::remove_file(&temp_file_path).expect("Unable to remove written test markdown file");
     }
+
+    #[test]
+    fn test_properties_round_trip() {
+        let mut app = setup_test_environment();
+        let markdown_content = r#"# Project Name
+## To Do
+- [ ] Fix login
+  estimate:: 3
+  owner:: alice
+  - Steps
+    - [ ] Reproduce
+      ticket:: AUTH-12
+- [ ] Wrapped
+  continuation line
+- [ ] Crash in parser
+  std::vector overflow
+  seen:: twice
+  on restart
+"#;
+        let temp_file_path = PathBuf::from("test_properties_round_trip.md");
+        fs::write(&temp_file_path, markdown_content).expect("Unable to write test markdown file");
+        app.load_md(&temp_file_path, 0);
+
+        let item = &app.boards[0].lists[0].items[0];
+        assert_eq!(item.text, "Fix login");
+        assert_eq!(item.properties["owner"], "alice");
+        assert_eq!(item.properties["estimate"], "3");
+        let sub_board = &app.boards[item.board.unwrap()];
+        assert_eq!(sub_board.lists[0].items[0].properties["ticket"], "AUTH-12");
+        assert_eq!(
+            app.boards[0].lists[0].items[1].text,
+            "Wrapped\ncontinuation line"
+        );
+        let item = &app.boards[0].lists[0].items[2];
+        assert_eq!(
+            item.text,
+            "Crash in parser\nstd::vector overflow\nseen:: twice\non restart"
+        );
+        assert!(item.properties.is_empty());
+
+        app.boards[0].lists[0].items.remove(1);
+        app.write_md(&temp_file_path);
+        let written_content =
+            fs::read_to_string(&temp_file_path).expect("Unable to read written test markdown file");
+        assert_eq!(
+            written_content,
+            markdown_content
+                .replace("- [ ] Wrapped\n  continuation line\n", "")
+                .replace(
+                    "\n  std::vector overflow\n  seen:: twice\n  on restart",
+                    " std::vector overflow seen:: twice on restart"
+                )
+        );
+
+        app.load_md(&temp_file_path, 0);
+        let item = &app.boards[0].lists[0].items[1];
+        assert_eq!(
+            item.text,
+            "Crash in parser std::vector overflow seen:: twice on restart"
+        );
+        assert!(item.properties.is_empty());
+        fs::remove_file(&temp_file_path).expect("Unable to remove test markdown file");
+    }
 }
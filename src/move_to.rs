@@ -0,0 +1,239 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Position,
+    style::Stylize,
+    text::{Line, Span},
+};
+use ratatui_textarea::TextArea;
+
+use crate::{
+    App, InputMode,
+    commands::{MoveToBoardCommand, item_label},
+    crossterm_to_input,
+    popup::show_popup,
+};
+
+/// A list an item can be moved to, named by the path of its board.
+pub struct MoveTarget {
+    pub board: usize,
+    pub list: usize,
+    pub label: String,
+}
+
+#[derive(Default)]
+pub struct MovePicker {
+    filter: TextArea<'static>,
+    selected: usize,
+}
+
+impl MovePicker {
+    fn filter(&self) -> String {
+        self.filter.lines().first().cloned().unwrap_or_default()
+    }
+}
+
+/// Whether the query letters appear in the text in order, ignoring case
+/// and spaces.
+pub fn fuzzy_matches(query: &str, text: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| chars.any(|c| c == q))
+}
+
+impl App {
+    /// Every list of the tree, parents before children.
+    pub fn move_targets(&self) -> Vec<MoveTarget> {
+        let separator = &self.config.board_config.path_separator.0;
+        let root = self.root_name();
+        self.walk_tree(0)
+            .into_iter()
+            .flat_map(|(board, trail)| {
+                let path = [&[root.as_str()], trail.as_slice()]
+                    .concat()
+                    .join(separator);
+                self.boards[board]
+                    .lists
+                    .iter()
+                    .enumerate()
+                    .map(move |(l, list)| MoveTarget {
+                        board,
+                        list: l,
+                        label: format!("{}{}{}", path, separator, list.name),
+                    })
+            })
+            .collect()
+    }
+
+    /// Lists the selected item can move to that match the filter. Its own
+    /// list and the lists of its sub-board tree are left out.
+    pub fn move_targets_for_selection(&self, filter: &str) -> Vec<MoveTarget> {
+        let board = self.active_board_index();
+        let Some(list) = self.active_board().current_list else {
+            return Vec::new();
+        };
+        let Some(item) = self
+            .active_board()
+            .current_list()
+            .and_then(|l| l.current_item())
+        else {
+            return Vec::new();
+        };
+        let own_tree = item.board.map(|b| self.tree_boards(b)).unwrap_or_default();
+        self.move_targets()
+            .into_iter()
+            .filter(|t| (t.board, t.list) != (board, list) && !own_tree.contains(&t.board))
+            .filter(|t| fuzzy_matches(filter, &t.label))
+            .collect()
+    }
+
+    pub fn open_move_picker(&mut self) {
+        self.load_linked_files();
+        if self
+            .active_board()
+            .current_list()
+            .and_then(|l| l.current_item())
+            .is_some()
+        {
+            self.move_picker = MovePicker::default();
+            self.input_mode = InputMode::Move;
+        }
+    }
+
+    /// Whether a list is full with WIP limits enforced, warning when it is.
+    pub fn refuses_items(&mut self, board: usize, list: usize) -> bool {
+        let list = &self.boards[board].lists[list];
+        let full = self.config.board_config.enforce_wip_limits && list.is_full();
+        if full {
+            self.warning = Some(format!("{} has reached its WIP limit", list.name));
+        }
+        full
+    }
+
+    /// Moves the selected item, with its sub-board, to the end of a list
+    /// anywhere in the tree.
+    pub fn move_selected_item_to(&mut self, to_board: usize, to_list: usize) {
+        let board = self.active_board_index();
+        let Some(list) = self.active_board().current_list else {
+            return;
+        };
+        let Some(item) = self.active_board().lists[list].selected_item_index else {
+            return;
+        };
+        if self.refuses_items(to_board, to_list) {
+            return;
+        }
+        // plain list names within the board, as logged by moves between
+        // neighbouring lists, and full paths across boards
+        let targets = self.move_targets();
+        let label = |b: usize, l: usize| {
+            if to_board == board {
+                return self.boards[b].lists[l].name.clone();
+            }
+            targets
+                .iter()
+                .find(|t| (t.board, t.list) == (b, l))
+                .map(|t| t.label.clone())
+                .unwrap_or_default()
+        };
+        let description = format!(
+            "{} from {} to {}",
+            item_label(&self.active_board().lists[list].items[item]),
+            label(board, list),
+            label(to_board, to_list)
+        );
+        self.apply_tree_command(Box::new(MoveToBoardCommand {
+            board,
+            list,
+            item,
+            to_board,
+            to_list,
+            to_item: self.boards[to_board].lists[to_list].items.len(),
+            description,
+            bookmark: self.active_board().get_selection_bookmark(),
+        }));
+    }
+
+    pub fn handle_move_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.input_mode = InputMode::Normal,
+            KeyCode::Down => self.move_picker.selected += 1,
+            KeyCode::Up => {
+                self.move_picker.selected = self.move_picker.selected.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+                let targets = self.move_targets_for_selection(&self.move_picker.filter());
+                if let Some(target) = targets.get(self.move_picker.selected) {
+                    self.move_selected_item_to(target.board, target.list);
+                }
+            }
+            _ => {
+                self.move_picker.filter.input(crossterm_to_input(key));
+                self.move_picker.selected = 0;
+            }
+        }
+        let targets = self
+            .move_targets_for_selection(&self.move_picker.filter())
+            .len();
+        self.move_picker.selected = self.move_picker.selected.min(targets.saturating_sub(1));
+    }
+
+    pub fn draw_move_popup(&self, frame: &mut Frame) {
+        let picker = &self.move_picker;
+        let filter = picker.filter();
+        let targets = self.move_targets_for_selection(&filter);
+        let rows = (frame.area().height as usize).saturating_sub(10).max(1);
+        let top = (picker.selected + 1).saturating_sub(rows);
+
+        let mut lines = vec![
+            Line::from(vec![Span::raw("Filter: ").dark_gray(), Span::raw(&filter)]),
+            Line::raw(""),
+        ];
+        lines.extend(
+            targets
+                .iter()
+                .enumerate()
+                .skip(top)
+                .take(rows)
+                .map(|(i, target)| {
+                    let line = Line::from(target.label.as_str());
+                    if i == picker.selected {
+                        line.reversed()
+                    } else {
+                        line
+                    }
+                }),
+        );
+        if targets.is_empty() {
+            lines.push(Line::from("No matching lists").dark_gray());
+        }
+        let width = frame.area().width.saturating_sub(4).min(80);
+        let area = show_popup(
+            frame,
+            "Move to",
+            width,
+            lines,
+            "Type to filter, Enter to move, Esc cancel",
+        );
+        let cursor = "Filter: ".len() + picker.filter.cursor().1;
+        frame.set_cursor_position(Position::new(area.x + cursor as u16, area.y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_matches() {
+        assert!(fuzzy_matches("", "Backlog"));
+        assert!(fuzzy_matches("bl", "Backlog"));
+        assert!(fuzzy_matches("rel done", "Release 〉 Done"));
+        assert!(!fuzzy_matches("lb", "Backlog"));
+        assert!(!fuzzy_matches("sub2", "root 〉 sub 1"));
+    }
+}
@@ -0,0 +1,158 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    style::Stylize,
+    text::Line,
+};
+
+use crate::{App, InputMode, popup::show_popup};
+
+/// A board to jump to, indented by its depth in the picker.
+pub struct BoardEntry {
+    pub depth: usize,
+    pub name: String,
+    pub board: usize,
+}
+
+#[derive(Default)]
+pub struct BoardPicker {
+    title: &'static str,
+    entries: Vec<BoardEntry>,
+    selected: usize,
+}
+
+impl App {
+    pub fn root_name(&self) -> String {
+        self.filename
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Root".to_string())
+    }
+
+    /// The root board followed by every board on the way to the active one.
+    pub fn breadcrumbs(&self) -> Vec<BoardEntry> {
+        let mut entries = vec![BoardEntry {
+            depth: 0,
+            name: self.root_name(),
+            board: 0,
+        }];
+        for (depth, name) in self.board_trail().into_iter().enumerate() {
+            entries.push(BoardEntry {
+                depth: depth + 1,
+                name,
+                board: self.board_path[self.board_path.len() - 1 - depth].board,
+            });
+        }
+        entries
+    }
+
+    /// Every board of the tree, named after the item owning it, parents
+    /// before children.
+    pub fn board_tree(&self) -> Vec<BoardEntry> {
+        self.walk_tree(0)
+            .into_iter()
+            .map(|(board, trail)| BoardEntry {
+                depth: trail.len(),
+                name: trail
+                    .last()
+                    .map_or_else(|| self.root_name(), |title| title.to_string()),
+                board,
+            })
+            .collect()
+    }
+
+    /// Opens the breadcrumb picker with the parent board selected.
+    pub fn open_breadcrumbs(&mut self) {
+        let entries = self.breadcrumbs();
+        self.board_picker = BoardPicker {
+            title: "Go to parent board",
+            selected: entries.len().saturating_sub(2),
+            entries,
+        };
+        self.input_mode = InputMode::Boards;
+    }
+
+    /// Opens the board tree picker with the active board selected.
+    pub fn open_board_tree(&mut self) {
+        self.load_linked_files();
+        let entries = self.board_tree();
+        let active = self.active_board_index();
+        self.board_picker = BoardPicker {
+            title: "Go to board",
+            selected: entries
+                .iter()
+                .position(|entry| entry.board == active)
+                .unwrap_or_default(),
+            entries,
+        };
+        self.input_mode = InputMode::Boards;
+    }
+
+    /// Opens a board of the tree, keeping the path to it for the header and
+    /// for going back up with Esc.
+    pub fn jump_to_board(&mut self, board: usize) {
+        let Some(board_path) = self.board_path_to(board) else {
+            return;
+        };
+        if let Some(list) = self.active_board_mut().current_list_mut() {
+            list.clear_selection();
+        }
+        self.board_path = board_path;
+        self.boards[board].config = self.config.board_config();
+    }
+
+    pub fn handle_boards_key(&mut self, key: KeyEvent) {
+        let picker = &mut self.board_picker;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.input_mode = InputMode::Normal,
+            KeyCode::Down | KeyCode::Char('j') => {
+                picker.selected = (picker.selected + 1).min(picker.entries.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                picker.selected = picker.selected.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+                if let Some(board) = picker.entries.get(picker.selected).map(|e| e.board) {
+                    self.jump_to_board(board);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_board_picker(&self, frame: &mut Frame) {
+        let picker = &self.board_picker;
+        let rows = (frame.area().height as usize).saturating_sub(10).max(1);
+        let top = (picker.selected + 1).saturating_sub(rows);
+        let active = self.active_board_index();
+        let lines = picker
+            .entries
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(rows)
+            .map(|(i, entry)| {
+                let line = Line::from(format!("{}{}", "  ".repeat(entry.depth), entry.name));
+                let line = if entry.board == active {
+                    line.yellow()
+                } else {
+                    line
+                };
+                if i == picker.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>();
+        let width = frame.area().width.saturating_sub(4).min(60);
+        show_popup(
+            frame,
+            picker.title,
+            width,
+            lines,
+            "Enter to open, Esc close",
+        );
+    }
+}
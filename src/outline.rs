@@ -0,0 +1,400 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+use ratatui_textarea::TextArea;
+
+use crate::{App, InputMode, commands::SelectionBookmark, crossterm_to_input};
+
+/// A line of the outline: a list of some board, or one of its items.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutlineRow {
+    List {
+        board: usize,
+        list: usize,
+        depth: usize,
+    },
+    Item {
+        board: usize,
+        list: usize,
+        item: usize,
+        depth: usize,
+    },
+}
+
+impl OutlineRow {
+    fn depth(&self) -> usize {
+        match *self {
+            OutlineRow::List { depth, .. } | OutlineRow::Item { depth, .. } => depth,
+        }
+    }
+
+    fn bookmark(&self) -> (usize, SelectionBookmark) {
+        match *self {
+            OutlineRow::List { board, list, .. } => (
+                board,
+                SelectionBookmark {
+                    list: Some(list),
+                    item: None,
+                },
+            ),
+            OutlineRow::Item {
+                board, list, item, ..
+            } => (
+                board,
+                SelectionBookmark {
+                    list: Some(list),
+                    item: Some(item),
+                },
+            ),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct OutlineView {
+    // sub-boards shown under their items
+    expanded: HashSet<usize>,
+    selected: usize,
+    scroll: usize,
+    edit: Option<TextArea<'static>>,
+}
+
+impl App {
+    /// Opens the outline of the whole tree, unfolded down to the selection
+    /// of the active board.
+    pub fn open_outline(&mut self) {
+        self.outline = OutlineView {
+            expanded: self.board_path.iter().map(|r| r.board).collect(),
+            ..Default::default()
+        };
+        self.sync_outline_selection();
+        self.input_mode = InputMode::Outline;
+    }
+
+    /// Lists and items of the tree in file order. Items of collapsed lists
+    /// and sub-boards that are not expanded are left out.
+    pub fn outline_rows(&self) -> Vec<OutlineRow> {
+        let mut rows = Vec::new();
+        self.collect_outline_rows(0, 0, &mut rows);
+        rows
+    }
+
+    fn collect_outline_rows(&self, board: usize, depth: usize, rows: &mut Vec<OutlineRow>) {
+        for (l, list) in self.boards[board].lists.iter().enumerate() {
+            rows.push(OutlineRow::List {
+                board,
+                list: l,
+                depth,
+            });
+            if list.collapsed {
+                continue;
+            }
+            for (i, item) in list.items.iter().enumerate() {
+                rows.push(OutlineRow::Item {
+                    board,
+                    list: l,
+                    item: i,
+                    depth: depth + 1,
+                });
+                if let Some(sub_board) = item.board
+                    && self.outline.expanded.contains(&sub_board)
+                {
+                    self.collect_outline_rows(sub_board, depth + 2, rows);
+                }
+            }
+        }
+    }
+
+    pub fn selected_outline_row(&self) -> Option<OutlineRow> {
+        self.outline_rows().get(self.outline.selected).copied()
+    }
+
+    // Opens the board of a row and selects its list or item there, so the
+    // board actions apply to it
+    fn select_outline_row(&mut self, row: OutlineRow) {
+        let (board, bookmark) = row.bookmark();
+        self.jump_to_board(board);
+        bookmark.select(&mut self.boards[board]);
+    }
+
+    // Follows the selection of the active board, which commands and undo
+    // move to the item they changed
+    fn sync_outline_selection(&mut self) {
+        let board = self.active_board_index();
+        let list = self.active_board().current_list;
+        let item = list.and_then(|l| self.active_board().lists[l].selected_item_index);
+        let rows = self.outline_rows();
+        let found = rows.iter().position(|row| match *row {
+            OutlineRow::List {
+                board: b, list: l, ..
+            } => b == board && Some(l) == list && item.is_none(),
+            OutlineRow::Item {
+                board: b,
+                list: l,
+                item: i,
+                ..
+            } => b == board && Some(l) == list && Some(i) == item,
+        });
+        self.outline.selected = found
+            .unwrap_or(self.outline.selected)
+            .min(rows.len().saturating_sub(1));
+    }
+
+    // Runs a board action on the item of the selected row
+    fn outline_item_action(&mut self, action: fn(&mut App)) {
+        if let Some(row @ OutlineRow::Item { .. }) = self.selected_outline_row() {
+            self.select_outline_row(row);
+            action(self);
+            self.sync_outline_selection();
+        }
+    }
+
+    // Shows the sub-board of the selected item, loading a linked file first,
+    // or expands a collapsed list
+    fn unfold_outline_row(&mut self) {
+        let Some(row) = self.selected_outline_row() else {
+            return;
+        };
+        self.select_outline_row(row);
+        match row {
+            OutlineRow::List { board, list, .. } => {
+                if self.boards[board].lists[list].collapsed {
+                    self.toggle_current_list_collapsed();
+                }
+            }
+            OutlineRow::Item { .. } => {
+                if let Some(board) = self.load_linked_board() {
+                    self.outline.expanded.insert(board);
+                }
+            }
+        }
+    }
+
+    // Hides the sub-board of the selected item or collapses the selected
+    // list, moving up to the parent row when there is nothing to fold
+    fn fold_outline_row(&mut self) {
+        let rows = self.outline_rows();
+        let Some(row) = rows.get(self.outline.selected).copied() else {
+            return;
+        };
+        match row {
+            OutlineRow::List { board, list, .. } if !self.boards[board].lists[list].collapsed => {
+                self.select_outline_row(row);
+                self.toggle_current_list_collapsed();
+                self.sync_outline_selection();
+                return;
+            }
+            OutlineRow::Item {
+                board, list, item, ..
+            } => {
+                if let Some(sub_board) = self.boards[board].lists[list].items[item].board
+                    && self.outline.expanded.remove(&sub_board)
+                {
+                    return;
+                }
+            }
+            _ => {}
+        }
+        if let Some(parent) = rows[..self.outline.selected]
+            .iter()
+            .rposition(|r| r.depth() < row.depth())
+        {
+            self.outline.selected = parent;
+        }
+    }
+
+    fn edit_outline_item(&mut self) {
+        if let Some(OutlineRow::Item {
+            board, list, item, ..
+        }) = self.selected_outline_row()
+        {
+            let title = self.boards[board].lists[list].items[item].title();
+            let mut textarea = TextArea::new(vec![title.to_string()]);
+            textarea.set_cursor_line_style(Style::default());
+            textarea.set_yank_text(self.yank_text());
+            textarea.move_cursor(ratatui_textarea::CursorMove::End);
+            self.outline.edit = Some(textarea);
+        }
+    }
+
+    // Changes the item title with the same staged command as the item
+    // editor, keeping the lines below it
+    fn commit_outline_edit(&mut self, title: String) {
+        let Some(row) = self.selected_outline_row() else {
+            return;
+        };
+        self.select_outline_row(row);
+        if let Some(cmd) = self.active_board_mut().edit_current_item() {
+            self.staged = Some(cmd);
+            let list = self.active_board().current_list.unwrap();
+            let item = self.active_board().lists[list].selected_item_index.unwrap();
+            let text = &mut self.active_board_mut().lists[list].items[item].text;
+            *text = match text.split_once('\n') {
+                Some((_, rest)) => format!("{}\n{}", title, rest),
+                None => title,
+            };
+            self.commit_board_command();
+        }
+    }
+
+    pub fn handle_outline_key(&mut self, key: KeyEvent) {
+        if let Some(textarea) = self.outline.edit.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.outline.edit = None,
+                KeyCode::Enter => {
+                    let title = textarea.lines().first().cloned().unwrap_or_default();
+                    self.outline.edit = None;
+                    self.commit_outline_edit(title);
+                }
+                _ => {
+                    textarea.input(crossterm_to_input(key));
+                }
+            }
+            return;
+        }
+
+        let last = self.outline_rows().len().saturating_sub(1);
+        if key.modifiers == KeyModifiers::CONTROL {
+            match key.code {
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.outline_item_action(App::move_to_prev_list)
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    self.outline_item_action(App::move_to_next_list)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.outline_item_action(App::deprioritize_selected_item)
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.outline_item_action(App::prioritize_selected_item)
+                }
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                if let Some(row) = self.selected_outline_row() {
+                    self.select_outline_row(row);
+                }
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.outline.selected = (self.outline.selected + 1).min(last);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.outline.selected = self.outline.selected.saturating_sub(1);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.outline.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.outline.selected = last,
+            KeyCode::Right | KeyCode::Char('l') => self.unfold_outline_row(),
+            KeyCode::Left | KeyCode::Char('h') => self.fold_outline_row(),
+            KeyCode::Enter => self.edit_outline_item(),
+            KeyCode::Char(' ') => self.outline_item_action(App::toggle_selected_item),
+            KeyCode::Char('d') => self.outline_item_action(App::delete_selected_item),
+            KeyCode::Char('u') => {
+                self.undo();
+                self.sync_outline_selection();
+            }
+            KeyCode::Char('r') => {
+                self.redo();
+                self.sync_outline_selection();
+            }
+            _ => {}
+        }
+    }
+
+    fn outline_line(&self, row: OutlineRow) -> (String, Line<'static>) {
+        match row {
+            OutlineRow::List {
+                board, list, depth, ..
+            } => {
+                let list = &self.boards[board].lists[list];
+                let marker = if list.collapsed { "▸ " } else { "▾ " };
+                let prefix = format!("{}{}", "  ".repeat(depth), marker);
+                let line = Line::from(vec![
+                    Span::raw(prefix.clone()),
+                    Span::raw(list.name.clone()).bold(),
+                    Span::raw(format!("  {}", list.items.len())).dark_gray(),
+                ]);
+                (prefix, line)
+            }
+            OutlineRow::Item {
+                board,
+                list,
+                item,
+                depth,
+            } => {
+                let item = &self.boards[board].lists[list].items[item];
+                let marker = match item.board {
+                    Some(b) if self.outline.expanded.contains(&b) => "▾ ",
+                    Some(_) => "▸ ",
+                    None if item.linked_file().is_some() => "▸ ",
+                    None => "  ",
+                };
+                let check = if item.done { "[x] " } else { "[ ] " };
+                let prefix = format!("{}{}{}", "  ".repeat(depth), marker, check);
+                let text = item.title().to_string();
+                let text = if item.done {
+                    Span::raw(text).dark_gray().crossed_out()
+                } else {
+                    Span::raw(text)
+                };
+                (prefix.clone(), Line::from(vec![Span::raw(prefix), text]))
+            }
+        }
+    }
+
+    pub fn draw_outline(&mut self, frame: &mut Frame, rect: Rect) {
+        let rows = self.outline_rows();
+        let height = rect.height as usize;
+        let view = &mut self.outline;
+        view.selected = view.selected.min(rows.len().saturating_sub(1));
+        if view.selected < view.scroll {
+            view.scroll = view.selected;
+        } else if view.selected >= view.scroll + height {
+            view.scroll = view.selected + 1 - height;
+        }
+        let (scroll, selected) = (view.scroll, view.selected);
+
+        let mut edit_prefix = None;
+        let lines = rows
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(height)
+            .map(|(i, row)| {
+                let (prefix, line) = self.outline_line(*row);
+                if i == selected {
+                    edit_prefix = Some(prefix);
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(Clear, rect);
+        frame.render_widget(Paragraph::new(lines), rect);
+
+        if let (Some(textarea), Some(prefix)) = (&self.outline.edit, edit_prefix) {
+            let x = prefix.chars().count() as u16;
+            let area = Rect::new(
+                rect.x + x,
+                rect.y + (selected - scroll) as u16,
+                rect.width.saturating_sub(x),
+                1,
+            );
+            frame.render_widget(Clear, area);
+            let mut textarea = textarea.clone();
+            textarea.set_style(Style::new().bg(Color::Rgb(0, 0, 0)).fg(Color::White));
+            frame.render_widget(&textarea, area);
+        }
+    }
+}
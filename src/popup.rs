@@ -0,0 +1,77 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// Renders a bordered popup in the middle of the frame with a dimmed footer
+/// line, sized to fit its content. Returns the area the lines are drawn in.
+pub fn show_popup(
+    frame: &mut Frame,
+    title: &str,
+    width: u16,
+    lines: Vec<Line>,
+    footer: &str,
+) -> Rect {
+    let area = centered(frame.area(), width, lines.len() as u16 + 6);
+
+    let block = Block::default()
+        .title(title.to_string())
+        .padding(Padding::new(2, 2, 1, 1))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White).bg(Color::Black));
+    let inner = block.inner(area);
+
+    let content = Paragraph::new(lines)
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .block(block);
+
+    let footer = Paragraph::new(footer.to_string()).dark_gray();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(content, area);
+    frame.render_widget(
+        footer.alignment(Alignment::Center),
+        Rect::new(
+            area.x + 1,
+            (area.y + area.height).saturating_sub(2),
+            area.width.saturating_sub(2),
+            1,
+        ),
+    );
+    inner
+}
+
+pub fn key_lines<'a>(header: &'a str, bindings: &[(&'a str, &'a str)]) -> Vec<Line<'a>> {
+    let mut lines = bindings
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::raw(*key).bold().yellow(),
+                Span::raw(" ".repeat(14usize.saturating_sub(key.chars().count()))),
+                Span::styled(*action, Style::new()),
+            ])
+        })
+        .collect::<Vec<Line>>();
+    lines.insert(
+        0,
+        Line::from(vec![
+            Span::raw("key").bold().underlined(),
+            Span::raw("           "),
+            Span::raw(header).bold().underlined(),
+        ]),
+    );
+    lines
+}
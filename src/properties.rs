@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Position,
+    style::Stylize,
+    text::{Line, Span},
+};
+use ratatui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    App, InputMode, crossterm_to_input,
+    list::{format_property, parse_property},
+    popup::show_popup,
+};
+
+#[derive(Default)]
+pub struct PropertyEditor {
+    selected: usize,
+    // the key being edited, or None for a new property, and the edited line
+    input: Option<(Option<String>, TextArea<'static>)>,
+}
+
+impl App {
+    fn current_item_properties(&self) -> Option<BTreeMap<String, String>> {
+        self.active_board()
+            .current_list()?
+            .current_item()
+            .map(|item| item.properties.clone())
+    }
+
+    fn change_current_item_properties(&mut self, properties: BTreeMap<String, String>) {
+        if let Some(cmd) = self
+            .active_board()
+            .change_current_item_properties(properties)
+        {
+            self.apply_board_command(cmd);
+        }
+    }
+
+    pub fn edit_properties(&mut self) {
+        if self.current_item_properties().is_some() {
+            self.property_editor = PropertyEditor::default();
+            self.input_mode = InputMode::Properties;
+        }
+    }
+
+    pub fn handle_properties_key(&mut self, key: KeyEvent) {
+        let Some(properties) = self.current_item_properties() else {
+            self.input_mode = InputMode::Normal;
+            return;
+        };
+        let editor = &mut self.property_editor;
+        if let Some((editing, textarea)) = editor.input.as_mut() {
+            match key.code {
+                KeyCode::Esc => editor.input = None,
+                KeyCode::Enter => {
+                    let line = textarea.lines().first().cloned().unwrap_or_default();
+                    let editing = editing.take();
+                    editor.input = None;
+                    if let Some((key, value)) = parse_property(&line) {
+                        let mut changed = properties;
+                        if let Some(editing) = editing {
+                            changed.remove(&editing);
+                        }
+                        editor.selected = changed.range(..key.clone()).count();
+                        changed.insert(key, value);
+                        self.change_current_item_properties(changed);
+                    }
+                }
+                _ => {
+                    textarea.input(crossterm_to_input(key));
+                }
+            }
+            return;
+        }
+
+        let selected = properties.iter().nth(editor.selected);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.input_mode = InputMode::Normal,
+            KeyCode::Down | KeyCode::Char('j') => {
+                editor.selected = (editor.selected + 1).min(properties.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                editor.selected = editor.selected.saturating_sub(1);
+            }
+            KeyCode::Char('o') => editor.input = Some((None, TextArea::default())),
+            KeyCode::Enter => {
+                if let Some((key, value)) = selected {
+                    let mut textarea = TextArea::new(vec![format_property(key, value)]);
+                    textarea.move_cursor(CursorMove::End);
+                    editor.input = Some((Some(key.clone()), textarea));
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some((key, _)) = selected {
+                    let mut changed = properties.clone();
+                    changed.remove(key);
+                    editor.selected = editor.selected.min(changed.len().saturating_sub(1));
+                    self.change_current_item_properties(changed);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_properties_popup(&self, frame: &mut Frame) {
+        let Some(properties) = self.current_item_properties() else {
+            return;
+        };
+        let editor = &self.property_editor;
+        let mut lines = properties
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| {
+                let line = Line::from(vec![
+                    Span::raw(key.clone()).bold().yellow(),
+                    Span::raw(format_property("", value)),
+                ]);
+                if i == editor.selected && editor.input.is_none() {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut cursor = None;
+        if let Some((editing, textarea)) = &editor.input {
+            let row = editing
+                .as_ref()
+                .and_then(|editing| properties.keys().position(|key| key == editing))
+                .unwrap_or(lines.len());
+            let line = Line::from(textarea.lines().first().cloned().unwrap_or_default());
+            if row < lines.len() {
+                lines[row] = line;
+            } else {
+                lines.push(line);
+            }
+            cursor = Some((row, textarea.cursor().1));
+        } else if lines.is_empty() {
+            lines.push(Line::from("No properties yet").dark_gray());
+        }
+
+        let footer = if editor.input.is_some() {
+            "Type key:: value, Enter to save, Esc to cancel"
+        } else {
+            "o add, Enter edit, d delete, Esc close"
+        };
+        let area = show_popup(frame, "Properties", 60, lines, footer);
+        if let Some((row, col)) = cursor {
+            frame.set_cursor_position(Position::new(area.x + col as u16, area.y + row as u16));
+        }
+    }
+}
@@ -0,0 +1,539 @@
+use std::collections::{HashMap, HashSet};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin},
+    style::{Color, Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Sparkline},
+};
+use time::{Date, Duration, PrimitiveDateTime};
+
+use crate::{App, InputMode, dates};
+
+const WEEKS: usize = 8;
+const DAYS: usize = 28;
+const RECENT_ITEMS: usize = 10;
+const COMPLETED: &str = "completed";
+
+// Colours of the cumulative flow bands, repeating for boards with more lists
+const FLOW_COLOURS: [Color; 6] = [
+    Color::LightGreen,
+    Color::LightBlue,
+    Color::Yellow,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::LightRed,
+];
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Created(String),
+    Moved(String, String),
+    Completed,
+    Reopened,
+    Removed,
+    Pasted(String),
+    // every done item of the board left it
+    Archived,
+}
+
+/// Reads back an item activity logged by a command, as the key the item is
+/// followed by, its title and the event. The key is the item id, or the
+/// title for items without one. Lists are needed to tell where the source
+/// list of a move ends when list names contain " to ".
+fn parse_activity(activity: &str, lists: &[String]) -> Option<(String, String, Event)> {
+    if activity.starts_with("archived ") && activity.ends_with(" done items") {
+        return Some((String::new(), String::new(), Event::Archived));
+    }
+    let (action, rest) = activity.split_once(" \"")?;
+    let (action, id) = match action.split_once(" [[") {
+        Some((action, id)) => (action, Some(id.strip_suffix("]]")?)),
+        None => (action, None),
+    };
+    let (item, rest) = if action == "demoted" {
+        rest.split_once("\" under ")?
+    } else {
+        rest.rsplit_once("\" ")?
+    };
+    let event = match action {
+        "created" => Event::Created(rest.strip_prefix("in ")?.to_string()),
+        "completed" => Event::Completed,
+        "reopened" => Event::Reopened,
+        "deleted" | "cut" | "demoted" => Event::Removed,
+        "pasted" => Event::Pasted(rest.strip_prefix("into ")?.to_string()),
+        "moved" => {
+            let rest = rest.strip_prefix("from ")?;
+            let splits = rest
+                .match_indices(" to ")
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            let i = splits
+                .iter()
+                .find(|&&i| lists.iter().any(|l| *l == rest[..i]))
+                .or(splits.first())?;
+            Event::Moved(rest[..*i].to_string(), rest[i + 4..].to_string())
+        }
+        _ => return None,
+    };
+    Some((id.unwrap_or(item).to_string(), item.to_string(), event))
+}
+
+/// Timed item events of a board, with undone activities taken out.
+fn board_events(
+    entries: &[(String, String, String)],
+    trail: &str,
+    lists: &[String],
+) -> Vec<(PrimitiveDateTime, String, String, Event)> {
+    let mut activities: Vec<(PrimitiveDateTime, &str)> = Vec::new();
+    for (time, _, activity) in entries.iter().filter(|(_, t, _)| t == trail) {
+        let Some(time) = dates::parse_time(time) else {
+            continue;
+        };
+        if let Some(undone) = activity.strip_prefix("undid: ") {
+            if let Some(i) = activities.iter().rposition(|(_, a)| *a == undone) {
+                activities.remove(i);
+            }
+        } else {
+            let activity = activity.strip_prefix("redid: ").unwrap_or(activity);
+            activities.push((time, activity));
+        }
+    }
+    activities
+        .into_iter()
+        .filter_map(|(time, activity)| {
+            let (key, item, event) = parse_activity(activity, lists)?;
+            Some((time, key, item, event))
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ItemTimes {
+    pub item: String,
+    pub completed: PrimitiveDateTime,
+    /// From creation to completion
+    pub lead: Option<Duration>,
+    /// From the first move between lists to completion
+    pub cycle: Option<Duration>,
+}
+
+#[derive(Default)]
+pub struct FlowReport {
+    /// Completed items, most recent first
+    pub items: Vec<ItemTimes>,
+    /// Items arriving in each list per week, oldest week first
+    pub throughput: Vec<(String, Vec<u64>)>,
+    /// Items in each list at the end of every day, oldest day first
+    pub flow: Vec<(String, Vec<u64>)>,
+}
+
+fn week_start(date: Date) -> Date {
+    date - Duration::days(date.weekday().number_days_from_monday() as i64)
+}
+
+impl FlowReport {
+    fn new(
+        events: &[(PrimitiveDateTime, String, String, Event)],
+        lists: &[String],
+        today: Date,
+    ) -> Self {
+        let mut report = FlowReport::default();
+
+        let mut times: HashMap<&str, (Option<PrimitiveDateTime>, Option<PrimitiveDateTime>)> =
+            HashMap::new();
+        // the latest title of every item, which may have been edited
+        let mut titles = HashMap::new();
+        let mut completed = HashMap::new();
+        for (time, key, item, event) in events.iter() {
+            if *event == Event::Archived {
+                continue;
+            }
+            titles.insert(key.as_str(), item.as_str());
+            let (created, started) = times.entry(key).or_default();
+            match event {
+                Event::Created(_) => *created = Some(*time),
+                Event::Moved(..) => *started = started.or(Some(*time)),
+                Event::Completed => {
+                    completed.insert(key.as_str(), *time);
+                }
+                Event::Reopened => {
+                    completed.remove(key.as_str());
+                }
+                Event::Removed | Event::Pasted(_) | Event::Archived => {}
+            }
+        }
+        report.items = completed
+            .into_iter()
+            .map(|(key, time)| {
+                let (created, started) = times[key];
+                ItemTimes {
+                    item: titles[key].to_string(),
+                    completed: time,
+                    lead: created.map(|created| time - created),
+                    cycle: started.map(|started| time - started),
+                }
+            })
+            .collect();
+        report
+            .items
+            .sort_by(|a, b| b.completed.cmp(&a.completed).then(a.item.cmp(&b.item)));
+
+        let first_week = week_start(today) - Duration::weeks(WEEKS as i64 - 1);
+        let mut rows = lists.to_vec();
+        rows.push(COMPLETED.to_string());
+        report.throughput = rows.into_iter().map(|row| (row, vec![0; WEEKS])).collect();
+        for (time, _, _, event) in events.iter() {
+            let row = match event {
+                Event::Created(list) | Event::Moved(_, list) | Event::Pasted(list) => list,
+                Event::Completed => COMPLETED,
+                _ => continue,
+            };
+            let week = (week_start(time.date()) - first_week).whole_weeks();
+            if let (Ok(week), Some((_, counts))) = (
+                usize::try_from(week),
+                report.throughput.iter_mut().find(|(r, _)| r == row),
+            ) && week < WEEKS
+            {
+                counts[week] += 1;
+            }
+        }
+
+        let first_day = today - Duration::days(DAYS as i64 - 1);
+        report.flow = lists.iter().map(|l| (l.clone(), vec![0; DAYS])).collect();
+        let mut places: HashMap<&str, &str> = HashMap::new();
+        let mut done = HashSet::new();
+        let mut events = events.iter().peekable();
+        for day in 0..DAYS {
+            let end = first_day + Duration::days(day as i64 + 1);
+            while let Some((_, key, _, event)) = events.next_if(|(time, ..)| time.date() < end) {
+                match event {
+                    Event::Created(list) | Event::Moved(_, list) | Event::Pasted(list) => {
+                        places.insert(key, list);
+                    }
+                    Event::Removed => {
+                        places.remove(key.as_str());
+                    }
+                    Event::Completed => {
+                        done.insert(key.as_str());
+                    }
+                    Event::Reopened => {
+                        done.remove(key.as_str());
+                    }
+                    Event::Archived => {
+                        places.retain(|key, _| !done.contains(key));
+                        done.clear();
+                    }
+                }
+            }
+            for list in places.values() {
+                if let Some((_, counts)) = report.flow.iter_mut().find(|(l, _)| l == list) {
+                    counts[day] += 1;
+                }
+            }
+        }
+        report
+    }
+}
+
+fn format_days(duration: Duration) -> String {
+    match duration.whole_days() {
+        0 => dates::format_duration(duration),
+        days => format!("{}d {}h", days, duration.whole_hours() % 24),
+    }
+}
+
+impl App {
+    /// Flow metrics of the active board, from its activity log history.
+    pub fn flow_report(&self, today: Date) -> FlowReport {
+        let lists = self
+            .active_board()
+            .lists
+            .iter()
+            .map(|list| list.name.clone())
+            .collect::<Vec<_>>();
+        let events = board_events(&self.activity_entries(), &self.activity_trail(), &lists);
+        FlowReport::new(&events, &lists, today)
+    }
+
+    pub fn open_flow_report(&mut self) {
+        let today = dates::today();
+        self.report = Some((today, self.flow_report(today)));
+        self.input_mode = InputMode::Report;
+    }
+
+    pub fn draw_report_popup(&self, frame: &mut Frame) {
+        let Some((today, report)) = &self.report else {
+            return;
+        };
+        let today = *today;
+        let area = frame.area().inner(Margin::new(4, 2));
+        let block = Block::default()
+            .title("Flow report")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White).bg(Color::Black));
+        frame.render_widget(Clear, area);
+        frame.render_widget(&block, area);
+        let inner = block.inner(area).inner(Margin::new(1, 0));
+
+        let mut lines = vec![
+            Line::from(format!(
+                "{:<10}  {:>8}  {:>8}  item",
+                "done", "lead", "cycle"
+            ))
+            .bold(),
+        ];
+        lines.extend(report.items.iter().take(RECENT_ITEMS).map(|times| {
+            let format = |d: Option<Duration>| d.map(format_days).unwrap_or("-".to_string());
+            Line::from(vec![
+                Span::raw(format!(
+                    "{:<10}  {:>8}  {:>8}  ",
+                    dates::format(times.completed.date()),
+                    format(times.lead),
+                    format(times.cycle)
+                ))
+                .dark_gray(),
+                Span::raw(times.item.clone()),
+            ])
+        }));
+        if report.items.is_empty() {
+            lines.push(Line::from("No completed items yet").dark_gray());
+        }
+        let [items_area, throughput_area, flow_area, footer_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Length(report.throughput.len() as u16 + 2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        frame.render_widget(Paragraph::new(lines), items_area);
+
+        let name_width = report
+            .throughput
+            .iter()
+            .map(|(row, _)| row.chars().count())
+            .max()
+            .unwrap_or_default();
+        let label_width = name_width as u16 + 6;
+        let [title_area, rows_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(throughput_area);
+        frame.render_widget(
+            Line::from(format!("Arrivals per week, last {} weeks", WEEKS)).bold(),
+            title_area,
+        );
+        for (i, (row, counts)) in report.throughput.iter().enumerate() {
+            if i as u16 >= rows_area.height {
+                break;
+            }
+            let [label, spark] = Layout::horizontal([
+                Constraint::Length(label_width),
+                Constraint::Length(WEEKS as u16),
+            ])
+            .areas(rows_area.rows().nth(i).unwrap_or_default());
+            frame.render_widget(
+                Line::from(format!(
+                    "{:<width$} {:>3}",
+                    row,
+                    counts.iter().sum::<u64>(),
+                    width = name_width
+                ))
+                .dark_gray(),
+                label,
+            );
+            frame.render_widget(
+                Sparkline::default()
+                    .data(counts.iter().copied())
+                    .style(Style::default().fg(Color::Yellow)),
+                spark,
+            );
+        }
+
+        // stacked bands with the last list at the bottom
+        let mut totals = vec![0; DAYS];
+        let mut points = Vec::new();
+        for (list, counts) in report.flow.iter().rev() {
+            for (total, count) in totals.iter_mut().zip(counts.iter()) {
+                *total += count;
+            }
+            let data = totals
+                .iter()
+                .enumerate()
+                .map(|(day, total)| (day as f64, *total as f64))
+                .collect::<Vec<_>>();
+            points.push((list.clone(), data));
+        }
+        let datasets = points
+            .iter()
+            .enumerate()
+            .map(|(i, (list, data))| {
+                Dataset::default()
+                    .name(list.clone())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(FLOW_COLOURS[i % FLOW_COLOURS.len()]))
+                    .data(data)
+            })
+            .collect::<Vec<_>>();
+        let max = totals.iter().max().copied().unwrap_or_default().max(1);
+        let first_day = today - Duration::days(DAYS as i64 - 1);
+        frame.render_widget(
+            Chart::new(datasets)
+                .block(Block::default().title(Line::from("Cumulative flow").bold()))
+                .x_axis(
+                    Axis::default()
+                        .bounds([0.0, DAYS as f64 - 1.0])
+                        .labels([dates::format(first_day), dates::format(today)])
+                        .style(Style::default().dark_gray()),
+                )
+                .y_axis(
+                    Axis::default()
+                        .bounds([0.0, max as f64])
+                        .labels(["0".to_string(), max.to_string()])
+                        .style(Style::default().dark_gray()),
+                ),
+            flow_area,
+        );
+
+        frame.render_widget(
+            Line::from("Press any key to close").dark_gray().centered(),
+            footer_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    fn lists() -> Vec<String> {
+        ["TODO", "READY TO GO", "DONE"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_activity() {
+        let lists = lists();
+        assert_eq!(
+            parse_activity("created \"Ship \"it\" now\" in TODO", &lists),
+            Some((
+                "Ship \"it\" now".to_string(),
+                "Ship \"it\" now".to_string(),
+                Event::Created("TODO".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_activity("moved [[k3x9a1]] \"Ship\" from READY TO GO to DONE", &lists),
+            Some((
+                "k3x9a1".to_string(),
+                "Ship".to_string(),
+                Event::Moved("READY TO GO".to_string(), "DONE".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_activity("cut \"Ship\" from TODO", &lists),
+            Some(("Ship".to_string(), "Ship".to_string(), Event::Removed))
+        );
+        assert_eq!(
+            parse_activity("demoted [[a1]] \"Ship\" under [[b2]] \"Plan\"", &lists),
+            Some(("a1".to_string(), "Ship".to_string(), Event::Removed))
+        );
+        assert_eq!(
+            parse_activity("archived 3 done items", &lists),
+            Some((String::new(), String::new(), Event::Archived))
+        );
+        assert_eq!(parse_activity("sorted TODO", &lists), None);
+    }
+
+    #[test]
+    fn test_flow_report() {
+        let entry = |time: &str, trail: &str, activity: &str| {
+            (time.to_string(), trail.to_string(), activity.to_string())
+        };
+        let entries = vec![
+            entry("2026-10-05 09:00", "", "created \"A\" in TODO"),
+            entry("2026-10-05 09:30", "", "created \"B\" in TODO"),
+            entry("2026-10-05 10:00", "sub", "created \"C\" in TODO"),
+            entry("2026-10-12 09:00", "", "moved \"A\" from TODO to DONE"),
+            entry("2026-10-12 10:00", "", "moved \"B\" from TODO to DONE"),
+            entry(
+                "2026-10-12 10:05",
+                "",
+                "undid: moved \"B\" from TODO to DONE",
+            ),
+            entry("2026-10-14 09:00", "", "completed \"A\" in DONE"),
+            entry("2026-10-15 09:00", "", "deleted \"B\" from TODO"),
+        ];
+        let lists = lists();
+        let events = board_events(&entries, "", &lists);
+        assert_eq!(events.len(), 5);
+
+        let report = FlowReport::new(&events, &lists, date!(2026 - 10 - 19));
+        assert_eq!(
+            report.items,
+            vec![ItemTimes {
+                item: "A".to_string(),
+                completed: datetime!(2026-10-14 09:00),
+                lead: Some(Duration::days(9)),
+                cycle: Some(Duration::days(2)),
+            }]
+        );
+
+        let weeks = |row: &str| {
+            let (_, counts) = report.throughput.iter().find(|(r, _)| r == row).unwrap();
+            counts[WEEKS - 3..].to_vec()
+        };
+        assert_eq!(weeks("TODO"), vec![2, 0, 0]);
+        assert_eq!(weeks("DONE"), vec![0, 1, 0]);
+        assert_eq!(weeks(COMPLETED), vec![0, 1, 0]);
+
+        let days = |list: &str| {
+            let (_, counts) = report.flow.iter().find(|(l, _)| l == list).unwrap();
+            // the 5th, 12th and 15th of October
+            vec![counts[DAYS - 15], counts[DAYS - 8], counts[DAYS - 5]]
+        };
+        assert_eq!(days("TODO"), vec![2, 1, 0]);
+        assert_eq!(days("DONE"), vec![0, 1, 1]);
+        assert_eq!(format_days(Duration::hours(50)), "2d 2h");
+        assert_eq!(format_days(Duration::minutes(90)), "1h 30m");
+    }
+
+    #[test]
+    fn test_flow_report_follows_ids() {
+        let entry =
+            |time: &str, activity: &str| (time.to_string(), String::new(), activity.to_string());
+        let entries = vec![
+            entry("2026-10-05 09:00", "created [[a1]] \"Fix\" in TODO"),
+            entry("2026-10-05 09:30", "created [[b2]] \"Fix\" in TODO"),
+            entry(
+                "2026-10-06 09:00",
+                "moved [[a1]] \"Fix login\" from TODO to DONE",
+            ),
+            entry("2026-10-07 09:00", "completed [[a1]] \"Fix login\" in DONE"),
+            entry("2026-10-14 09:00", "archived 1 done items"),
+        ];
+        let lists = lists();
+        let events = board_events(&entries, "", &lists);
+        let report = FlowReport::new(&events, &lists, date!(2026 - 10 - 19));
+        assert_eq!(
+            report.items,
+            vec![ItemTimes {
+                item: "Fix login".to_string(),
+                completed: datetime!(2026-10-07 09:00),
+                lead: Some(Duration::days(2)),
+                cycle: Some(Duration::days(1)),
+            }]
+        );
+
+        let days = |list: &str| {
+            let (_, counts) = report.flow.iter().find(|(l, _)| l == list).unwrap();
+            // the 5th, 7th and 14th of October
+            vec![counts[DAYS - 15], counts[DAYS - 13], counts[DAYS - 6]]
+        };
+        assert_eq!(days("TODO"), vec![2, 1, 1]);
+        assert_eq!(days("DONE"), vec![0, 1, 0]);
+    }
+}
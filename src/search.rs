@@ -1,8 +1,8 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Position, Rect},
     widgets::{Clear, Paragraph},
-    Frame,
 };
 use ratatui_textarea::{Input, Key, TextArea};
 
@@ -44,12 +44,11 @@ impl FilteredBoardView {
     pub fn navigate_actual_board(&self, board: &mut Board, key: &KeyEvent) {
         match key.code {
             KeyCode::Down => {
-                if let Some(current_list_index) = board.current_list {
-                    if board.get_current_selection_index()
+                if let Some(current_list_index) = board.current_list
+                    && board.get_current_selection_index()
                         < self.view_items_in_list(current_list_index) - 1
-                    {
-                        board.move_down()
-                    }
+                {
+                    board.move_down()
                 }
             }
             KeyCode::Up => board.move_up(),
@@ -57,11 +56,14 @@ impl FilteredBoardView {
                 if let Some(mut current_list_index) = board.current_list {
                     if self.view_has_items_right_of(current_list_index) {
                         board.move_right();
-                        current_list_index += 1;
+                        current_list_index = board.current_list.unwrap();
                     }
                     while self.view_list_on_right_is_empty(current_list_index) {
                         board.move_right();
-                        current_list_index += 1;
+                        if board.current_list == Some(current_list_index) {
+                            break;
+                        }
+                        current_list_index = board.current_list.unwrap();
                     }
                 }
             }
@@ -69,12 +71,15 @@ impl FilteredBoardView {
                 if let Some(mut current_list_index) = board.current_list {
                     if self.view_has_items_left_of(current_list_index) {
                         board.move_left();
-                        current_list_index -= 1;
+                        current_list_index = board.current_list.unwrap();
                     }
 
                     while self.view_list_on_left_is_empty(current_list_index) {
                         board.move_left();
-                        current_list_index -= 1;
+                        if board.current_list == Some(current_list_index) {
+                            break;
+                        }
+                        current_list_index = board.current_list.unwrap();
                     }
                 }
             }
@@ -121,7 +126,7 @@ impl FilteredBoardView {
     }
 
     fn view_list_on_right_is_empty(&self, current_list_index: usize) -> bool {
-        self.board_view[current_list_index].len() == 0
+        self.board_view[current_list_index].is_empty()
             && current_list_index < self.board_view.len() - 1
     }
     fn view_list_on_left_is_empty(&self, current_list_index: usize) -> bool {
@@ -156,13 +161,13 @@ impl SearchController {
         frame.render_widget(Clear, area);
         let ratatui_textarea::DataCursor(_, col) = self.textarea.cursor();
         frame.render_widget(
-            Paragraph::new(format!("/{}", self.textarea.lines().first().cloned().unwrap_or_default())),
+            Paragraph::new(format!(
+                "/{}",
+                self.textarea.lines().first().cloned().unwrap_or_default()
+            )),
             area,
         );
-        frame.set_cursor_position(Position::new(
-            1 + col as u16,
-            frame.area().height - 1,
-        ));
+        frame.set_cursor_position(Position::new(1 + col as u16, frame.area().height - 1));
     }
 
     pub fn reflect(&self, board: &Board) -> FilteredBoardView {
@@ -172,10 +177,7 @@ impl SearchController {
             let mut list_view: Vec<(usize, usize)> = Vec::new();
             let mut partial_index: usize = 0;
             for (index, i) in l.items.iter().enumerate() {
-                if i.text
-                    .to_lowercase()
-                    .contains(&search_text.to_lowercase())
-                {
+                if i.matches(&search_text, &board.config) {
                     list_view.push((partial_index, index));
                     partial_index += 1;
                 }
@@ -193,4 +195,18 @@ impl SearchController {
     pub fn clear(&mut self) {
         self.textarea = TextArea::default();
     }
+
+    pub fn textarea(&self) -> &TextArea<'static> {
+        &self.textarea
+    }
+
+    pub fn complete_tag(&mut self, tag: &str) -> String {
+        crate::complete::complete_tag(&mut self.textarea, tag);
+        self.textarea.lines().first().cloned().unwrap_or_default()
+    }
+
+    pub fn start_with(&mut self, query: &str) {
+        self.textarea = TextArea::new(vec![query.to_string()]);
+        self.textarea.move_cursor(ratatui_textarea::CursorMove::End);
+    }
 }
@@ -0,0 +1,45 @@
+use ratatui::Frame;
+
+use crate::popup::{key_lines, show_popup};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    DoneLast,
+    Alphabetical,
+    DueDate,
+    Tag,
+    Priority,
+    Progress,
+}
+
+const SORT_KEYS: [(&str, &str, SortKey); 6] = [
+    ("d", "Open items first, done last", SortKey::DoneLast),
+    ("a", "Alphabetically", SortKey::Alphabetical),
+    ("t", "By due date, earliest first", SortKey::DueDate),
+    ("#", "By first tag", SortKey::Tag),
+    ("!", "By priority, highest first", SortKey::Priority),
+    ("p", "By sub-board progress", SortKey::Progress),
+];
+
+impl SortKey {
+    pub fn from_char(c: char) -> Option<SortKey> {
+        SORT_KEYS
+            .iter()
+            .find(|(key, _, _)| key.starts_with(c))
+            .map(|(_, _, sort_key)| *sort_key)
+    }
+}
+
+pub fn show_sort_popup(frame: &mut Frame) {
+    let bindings = SORT_KEYS
+        .iter()
+        .map(|(key, action, _)| (*key, *action))
+        .collect::<Vec<_>>();
+    show_popup(
+        frame,
+        "Sort list",
+        50,
+        key_lines("Order", &bindings),
+        "Press any other key to cancel",
+    );
+}
@@ -0,0 +1,191 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Position,
+    style::{Color, Stylize},
+    text::{Line, Span},
+};
+use ratatui_textarea::TextArea;
+
+use crate::{
+    App, InputMode,
+    commands::{ChangeTextCommand, Command, CompositeCommand},
+    config::Style,
+    crossterm_to_input,
+    list::tag_style,
+    popup::show_popup,
+};
+
+// Colours `c` cycles a tag through before going back to the generic style
+const TAG_COLOURS: [Color; 6] = [
+    Color::Yellow,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+#[derive(Default)]
+pub struct TagManager {
+    selected: usize,
+    rename: Option<TextArea<'static>>,
+}
+
+impl App {
+    pub fn open_tag_manager(&mut self) {
+        self.load_linked_files();
+        self.tag_manager = TagManager::default();
+        self.input_mode = InputMode::Tags;
+    }
+
+    /// Renames a tag on every item of the tree as a single undoable step.
+    /// Renaming to a tag that is already in use merges the two.
+    pub fn rename_tag(&mut self, old: &str, new: &str) {
+        self.load_linked_files();
+        let mut commands: Vec<(usize, Box<dyn Command>)> = Vec::new();
+        for board in self.tree_boards(0) {
+            for (l, list) in self.boards[board].lists.iter().enumerate() {
+                for (i, item) in list.items.iter().enumerate() {
+                    if let Some(text) = item.renamed_tag(old, new) {
+                        commands.push((
+                            board,
+                            Box::new(ChangeTextCommand {
+                                list: l,
+                                item: i,
+                                old: item.text.clone(),
+                                new: text,
+                                bookmark: self.boards[board].get_selection_bookmark(),
+                            }),
+                        ));
+                    }
+                }
+            }
+        }
+        if !commands.is_empty() {
+            self.apply_tree_command(Box::new(CompositeCommand { commands }));
+        }
+    }
+
+    /// Sets the colour of a tag, or returns it to the generic tag style.
+    pub fn set_tag_colour(&mut self, tag: &str, colour: Option<Color>) {
+        match colour {
+            Some(colour) => {
+                self.config
+                    .tags
+                    .insert(tag.to_string(), Style::with_fg(colour));
+            }
+            None => {
+                self.config.tags.remove(tag);
+            }
+        }
+        for board in self.boards.iter_mut() {
+            board.config.tag_styles = self.config.tags.clone();
+        }
+    }
+
+    fn cycle_tag_colour(&mut self, tag: &str) {
+        let current = self.config.tags.get(tag).map(|style| style.fg);
+        let next = match current.and_then(|c| TAG_COLOURS.iter().position(|t| *t == c)) {
+            Some(i) => TAG_COLOURS.get(i + 1).copied(),
+            None => Some(TAG_COLOURS[0]),
+        };
+        self.set_tag_colour(tag, next);
+        self.save_config();
+    }
+
+    pub fn handle_tags_key(&mut self, key: KeyEvent) {
+        let tags = self.ranked_tags();
+        let selected = tags
+            .get(self.tag_manager.selected)
+            .map(|(tag, _)| tag.clone());
+        if let Some(textarea) = self.tag_manager.rename.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.tag_manager.rename = None,
+                KeyCode::Enter => {
+                    let line = textarea.lines().first().cloned().unwrap_or_default();
+                    let new = line.trim().trim_start_matches('#');
+                    self.tag_manager.rename = None;
+                    if let Some(old) = selected
+                        && !new.is_empty()
+                        && !new.contains(|c: char| c.is_whitespace() || c == '#')
+                    {
+                        self.rename_tag(&old, new);
+                        self.tag_manager.selected = self
+                            .ranked_tags()
+                            .iter()
+                            .position(|(tag, _)| tag == new)
+                            .unwrap_or_default();
+                    }
+                }
+                _ => {
+                    textarea.input(crossterm_to_input(key));
+                }
+            }
+            return;
+        }
+
+        let manager = &mut self.tag_manager;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.input_mode = InputMode::Normal,
+            KeyCode::Down | KeyCode::Char('j') => {
+                manager.selected = (manager.selected + 1).min(tags.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                manager.selected = manager.selected.saturating_sub(1);
+            }
+            KeyCode::Char('r') if selected.is_some() => {
+                manager.rename = Some(TextArea::default());
+            }
+            KeyCode::Char('c') => {
+                if let Some(tag) = selected {
+                    self.cycle_tag_colour(&tag);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_tag_manager(&self, frame: &mut Frame) {
+        let config = self.config.board_config();
+        let manager = &self.tag_manager;
+        let mut lines = self
+            .ranked_tags()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (tag, count))| {
+                let style = tag_style(&tag, &config);
+                let line = Line::from(vec![
+                    Span::raw(format!("{:>4}  ", count)).dark_gray(),
+                    Span::raw(format!("#{}", tag)).fg(style.fg).bg(style.bg),
+                ]);
+                if i == manager.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            lines.push(Line::from("No tags yet").dark_gray());
+        }
+
+        let mut cursor = None;
+        if let Some(textarea) = &manager.rename {
+            lines.push(Line::raw(""));
+            let input = textarea.lines().first().cloned().unwrap_or_default();
+            lines.push(Line::from(format!("Rename to #{}", input)));
+            cursor = Some((lines.len() - 1, "Rename to #".len() + textarea.cursor().1));
+        }
+
+        let footer = if manager.rename.is_some() {
+            "Enter to rename or merge, Esc to cancel"
+        } else {
+            "r rename, c colour, Esc close"
+        };
+        let area = show_popup(frame, "Tags", 50, lines, footer);
+        if let Some((row, col)) = cursor {
+            frame.set_cursor_position(Position::new(area.x + col as u16, area.y + row as u16));
+        }
+    }
+}
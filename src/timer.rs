@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    Frame,
+    style::Stylize,
+    text::{Line, Span},
+};
+use time::{Duration, PrimitiveDateTime};
+
+use crate::{
+    App, BoardItem,
+    commands::{ChangePropertiesCommand, Command, CompositeCommand},
+    dates,
+    popup::show_popup,
+};
+
+// Item properties holding the tracked time and the start of a running timer
+pub const SPENT_PROPERTY: &str = "spent";
+pub const STARTED_PROPERTY: &str = "started";
+
+/// Named totals of tracked time.
+pub type TimeTotals = Vec<(String, Duration)>;
+
+fn timer_start(item: &BoardItem) -> Option<PrimitiveDateTime> {
+    dates::parse_time(item.properties.get(STARTED_PROPERTY)?)
+}
+
+/// Time tracked against an item, including its running timer.
+pub fn tracked_time(item: &BoardItem, now: PrimitiveDateTime) -> Duration {
+    let spent = item
+        .properties
+        .get(SPENT_PROPERTY)
+        .and_then(|spent| dates::parse_duration(spent))
+        .unwrap_or_default();
+    let running = timer_start(item)
+        .map(|start| now - start)
+        .unwrap_or_default();
+    spent + running.max(Duration::ZERO)
+}
+
+impl App {
+    /// The board, list and item whose timer is running.
+    pub fn running_timer(&self) -> Option<(usize, usize, usize)> {
+        self.tree_boards(0).into_iter().find_map(|board| {
+            self.boards[board]
+                .lists
+                .iter()
+                .enumerate()
+                .find_map(|(l, list)| {
+                    list.items
+                        .iter()
+                        .position(|item| item.properties.contains_key(STARTED_PROPERTY))
+                        .map(|i| (board, l, i))
+                })
+        })
+    }
+
+    pub fn toggle_timer(&mut self) {
+        self.toggle_timer_at(dates::now());
+    }
+
+    /// Starts the timer of the selected item, stopping any other running
+    /// timer, or stops it when it is already running. Stopping a timer adds
+    /// the elapsed time to the item `spent` property.
+    pub fn toggle_timer_at(&mut self, now: PrimitiveDateTime) {
+        self.load_linked_files();
+        let board = self.active_board_index();
+        let Some(list) = self.active_board().current_list else {
+            return;
+        };
+        let Some(item) = self.active_board().lists[list].selected_item_index else {
+            return;
+        };
+        let running = self.running_timer();
+        let mut commands = Vec::new();
+        if let Some((b, l, i)) = running {
+            let item = &self.boards[b].lists[l].items[i];
+            let mut stopped = item.properties.clone();
+            stopped.remove(STARTED_PROPERTY);
+            stopped.insert(
+                SPENT_PROPERTY.to_string(),
+                dates::format_exact_duration(tracked_time(item, now)),
+            );
+            commands.push(self.properties_command(b, l, i, stopped));
+        }
+        if running != Some((board, list, item)) {
+            let mut started = self.boards[board].lists[list].items[item]
+                .properties
+                .clone();
+            started.insert(STARTED_PROPERTY.to_string(), dates::format_time(now));
+            commands.push(self.properties_command(board, list, item, started));
+        }
+        self.apply_tree_command(Box::new(CompositeCommand { commands }));
+    }
+
+    fn properties_command(
+        &self,
+        board: usize,
+        list: usize,
+        item: usize,
+        properties: BTreeMap<String, String>,
+    ) -> (usize, Box<dyn Command>) {
+        (
+            board,
+            Box::new(ChangePropertiesCommand {
+                list,
+                item,
+                old: self.boards[board].lists[list].items[item]
+                    .properties
+                    .clone(),
+                new: properties,
+                bookmark: self.boards[board].get_selection_bookmark(),
+            }),
+        )
+    }
+
+    /// The running timer item and its elapsed time, for the status line.
+    pub fn timer_status(&self, now: PrimitiveDateTime) -> Option<String> {
+        let (board, list, item) = self.running_timer()?;
+        let item = &self.boards[board].lists[list].items[item];
+        let elapsed = now - timer_start(item)?;
+        Some(format!(
+            "⏱ {} {}",
+            item.title(),
+            dates::format_duration(elapsed)
+        ))
+    }
+
+    fn tracked_tree_time(&self, item: &BoardItem, now: PrimitiveDateTime) -> Duration {
+        let sub_board = item
+            .board
+            .map(|board| {
+                self.tree_items(board)
+                    .into_iter()
+                    .map(|item| tracked_time(item, now))
+                    .sum::<Duration>()
+            })
+            .unwrap_or_default();
+        tracked_time(item, now) + sub_board
+    }
+
+    /// Time tracked in each list of the active board, and in each of its
+    /// sub-board trees, counting the parent item along with its sub-board.
+    pub fn time_report(&self, now: PrimitiveDateTime) -> (TimeTotals, TimeTotals) {
+        let board = self.active_board();
+        let lists = board
+            .lists
+            .iter()
+            .map(|list| {
+                let total = list
+                    .items
+                    .iter()
+                    .map(|item| self.tracked_tree_time(item, now))
+                    .sum();
+                (list.name.clone(), total)
+            })
+            .collect();
+        let sub_boards = board
+            .lists
+            .iter()
+            .flat_map(|list| list.items.iter())
+            .filter(|item| item.board.is_some())
+            .map(|item| (item.title().to_string(), self.tracked_tree_time(item, now)))
+            .collect();
+        (lists, sub_boards)
+    }
+
+    pub fn draw_time_report_popup(&self, frame: &mut Frame) {
+        let (lists, sub_boards) = self.time_report(dates::now());
+        let row = |(name, total): (String, Duration)| {
+            Line::from(vec![
+                Span::raw(format!("{:>8}  ", dates::format_duration(total)))
+                    .bold()
+                    .yellow(),
+                Span::raw(name),
+            ])
+        };
+        let mut lines = vec![Line::from("Lists").bold()];
+        lines.extend(lists.into_iter().map(row));
+        if !sub_boards.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from("Sub-boards").bold());
+            lines.extend(sub_boards.into_iter().map(row));
+        }
+        show_popup(frame, "Tracked time", 50, lines, "Press any key to close");
+    }
+}